@@ -0,0 +1,70 @@
+use chrono::serde::ts_milliseconds;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::user::User;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub id: u64,
+    #[serde(with = "ts_milliseconds")]
+    pub created_date: DateTime<Utc>,
+    pub user: User,
+    pub action: ActivityAction,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ActivityAction {
+    Approved,
+    Unapproved,
+    Reviewed,
+    Commented,
+    #[serde(other)]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!([
+            {
+                "id": 101,
+                "createdDate": 1359065920000u64,
+                "user": {
+                    "name": "jcitizen",
+                    "emailAddress": "jane@example.com",
+                    "id": 101,
+                    "displayName": "Jane Citizen"
+                },
+                "action": "COMMENTED",
+                "commentAction": "ADDED"
+            },
+            {
+                "id": 102,
+                "createdDate": 1359085920000u64,
+                "user": {
+                    "name": "jcitizen",
+                    "id": 101,
+                    "displayName": "Jane Citizen"
+                },
+                "action": "RESCOPED"
+            }
+        ]);
+
+        let activities: Vec<Activity> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(activities[0].user.name, "jcitizen");
+        assert_eq!(activities[0].action, ActivityAction::Commented);
+        assert_eq!(
+            activities[0].created_date,
+            Utc.with_ymd_and_hms(2013, 1, 24, 22, 18, 40).unwrap()
+        );
+        assert_eq!(activities[1].action, ActivityAction::Other);
+    }
+}
@@ -2,13 +2,14 @@ use std::collections::HashMap;
 
 use super::build_status::BuildStats;
 use super::repo::Repo;
-use super::{PullRequest, RepoId};
+use super::{Activity, MergeStatus, ParticipantRole, PullRequest, RepoId};
 use crate::git::{AuthDomainConfig, BaseUrlConfig};
 use crate::Authenticator;
 
 use chipp_http::curl::easy::Auth;
 use chipp_http::json::parse_json;
 use chipp_http::{Error, HttpClient, HttpMethod};
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
 
 pub struct Client<'a> {
@@ -37,6 +38,19 @@ impl Client<'_> {
         self.inner.get(vec!["api", "1.0", "users", username]).await
     }
 
+    pub async fn get_repo(&self, repo_id: &RepoId) -> Result<Repo, Error> {
+        self.inner
+            .get(vec![
+                "api",
+                "1.0",
+                "projects",
+                &repo_id.project,
+                "repos",
+                &repo_id.name,
+            ])
+            .await
+    }
+
     pub async fn create_repo(&self, repo_id: RepoId) -> Result<Repo, Error> {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -67,11 +81,11 @@ impl Client<'_> {
     pub async fn find_open_prs(
         &self,
         repo_id: &RepoId,
-        author: Option<String>,
+        participant: Option<(String, ParticipantRole)>,
     ) -> Result<PageResponse<PullRequest>, Error> {
         let params: Vec<(&str, &str)>;
-        if let Some(author) = author.as_ref() {
-            params = vec![("username.1", author), ("role.1", "AUTHOR")];
+        if let Some((username, role)) = participant.as_ref() {
+            params = vec![("username.1", username), ("role.1", role.as_str())];
         } else {
             params = vec![];
         }
@@ -92,6 +106,33 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn find_closed_prs(
+        &self,
+        repo_id: &RepoId,
+        state: &str,
+        start: u16,
+    ) -> Result<PageResponse<PullRequest>, Error> {
+        self.inner
+            .get_with_params(
+                vec![
+                    "api",
+                    "1.0",
+                    "projects",
+                    &repo_id.project,
+                    "repos",
+                    &repo_id.name,
+                    "pull-requests",
+                ],
+                &[
+                    ("state", state),
+                    ("order", "NEWEST"),
+                    ("start", &start.to_string()),
+                    ("limit", "100"),
+                ],
+            )
+            .await
+    }
+
     pub async fn find_prs_for_branch(
         &self,
         branch: &str,
@@ -139,6 +180,162 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn create_pr(
+        &self,
+        repo_id: &RepoId,
+        title: &str,
+        from_branch: &str,
+        to_branch: &str,
+        draft: bool,
+    ) -> Result<PullRequest, Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct CreateBody<'a> {
+            title: &'a str,
+            from_ref: RefBody<'a>,
+            to_ref: RefBody<'a>,
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            draft: bool,
+        }
+
+        #[derive(Serialize)]
+        struct RefBody<'a> {
+            id: String,
+            repository: RepositoryBody<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct RepositoryBody<'a> {
+            slug: &'a str,
+            project: ProjectBody<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct ProjectBody<'a> {
+            key: &'a str,
+        }
+
+        let reference = |branch: &str| RefBody {
+            id: format!("refs/heads/{branch}"),
+            repository: RepositoryBody {
+                slug: &repo_id.name,
+                project: ProjectBody {
+                    key: &repo_id.project,
+                },
+            },
+        };
+
+        let mut request = self.inner.new_request(&[
+            "api",
+            "1.0",
+            "projects",
+            &repo_id.project,
+            "repos",
+            &repo_id.name,
+            "pull-requests",
+        ]);
+
+        request.set_json_body(&CreateBody {
+            title,
+            from_ref: reference(from_branch),
+            to_ref: reference(to_branch),
+            draft,
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner.perform_request(request, parse_json).await
+    }
+
+    pub async fn merge_pr(
+        &self,
+        id: u16,
+        version: u32,
+        repo_id: &RepoId,
+    ) -> Result<PullRequest, Error> {
+        let mut request = self.inner.new_request_with_params(
+            &[
+                "api",
+                "1.0",
+                "projects",
+                &repo_id.project,
+                "repos",
+                &repo_id.name,
+                "pull-requests",
+                &id.to_string(),
+                "merge",
+            ],
+            &[("version", version.to_string())],
+        );
+        request.set_method(HttpMethod::Post);
+
+        self.inner.perform_request(request, parse_json).await
+    }
+
+    pub async fn get_pr_merge_status(
+        &self,
+        id: u16,
+        repo_id: &RepoId,
+    ) -> Result<MergeStatus, Error> {
+        self.inner
+            .get(vec![
+                "api",
+                "1.0",
+                "projects",
+                &repo_id.project,
+                "repos",
+                &repo_id.name,
+                "pull-requests",
+                &id.to_string(),
+                "merge",
+            ])
+            .await
+    }
+
+    pub async fn get_pr_activities(
+        &self,
+        id: u16,
+        repo_id: &RepoId,
+    ) -> Result<PageResponse<Activity>, Error> {
+        self.inner
+            .get_with_params(
+                vec![
+                    "api",
+                    "1.0",
+                    "projects",
+                    &repo_id.project,
+                    "repos",
+                    &repo_id.name,
+                    "pull-requests",
+                    &id.to_string(),
+                    "activities",
+                ],
+                &[("limit", "500")],
+            )
+            .await
+    }
+
+    pub async fn get_pr_changes_count(&self, id: u16, repo_id: &RepoId) -> Result<u16, Error> {
+        let response: PageResponse<IgnoredAny> = self
+            .inner
+            .get_with_params(
+                vec![
+                    "api",
+                    "1.0",
+                    "projects",
+                    &repo_id.project,
+                    "repos",
+                    &repo_id.name,
+                    "pull-requests",
+                    &id.to_string(),
+                    "changes",
+                ],
+                &[("limit", "1000")],
+            )
+            .await?;
+
+        Ok(response.size)
+    }
+
     pub async fn get_commit_build_stats(&self, sha: &str) -> Result<BuildStats, Error> {
         self.inner
             .get(&["build-status", "latest", "commits", "stats", sha])
@@ -168,4 +365,6 @@ pub struct PageResponse<V> {
     pub size: u16,
     pub start: u16,
     pub limit: u16,
+    /// Missing on the last page.
+    pub next_page_start: Option<u16>,
 }
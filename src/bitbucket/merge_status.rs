@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeStatus {
+    pub can_merge: bool,
+    pub conflicted: bool,
+    pub outcome: Option<MergeOutcome>,
+    #[serde(default)]
+    pub vetoes: Vec<Veto>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MergeOutcome {
+    Clean,
+    Conflicted,
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Veto {
+    pub summary_message: String,
+    pub detailed_message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!({
+            "canMerge": false,
+            "conflicted": false,
+            "outcome": "CLEAN",
+            "vetoes": [
+                {
+                    "summaryMessage": "Not all required builds are successful yet",
+                    "detailedMessage": "You cannot merge this pull request while it has failed builds."
+                }
+            ]
+        });
+
+        let status: MergeStatus = serde_json::from_value(json).unwrap();
+
+        assert!(!status.can_merge);
+        assert!(!status.conflicted);
+        assert_eq!(status.outcome, Some(MergeOutcome::Clean));
+        assert_eq!(status.vetoes.len(), 1);
+        assert_eq!(
+            status.vetoes[0].summary_message,
+            "Not all required builds are successful yet"
+        );
+    }
+}
@@ -1,16 +1,20 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod activity;
 mod build_status;
 mod client;
+mod merge_status;
 mod pull_request;
 mod repo;
 mod repo_id;
 mod user;
 
+pub use activity::{Activity, ActivityAction};
 pub use build_status::MergedBuildStatus;
 pub use client::Client;
-pub use pull_request::PullRequest;
+pub use merge_status::{MergeOutcome, MergeStatus, Veto};
+pub use pull_request::{ParticipantRole, PullRequest, PullRequestState};
 pub use repo_id::RepoId;
 
 use crate::git::{find_remote_by_priority, BaseUrlConfig};
@@ -1,6 +1,6 @@
 use super::user::Actor;
 use crate::bitbucket::repo::Repo;
-use chrono::serde::ts_milliseconds;
+use chrono::serde::{ts_milliseconds, ts_milliseconds_option};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use url::Url;
@@ -9,12 +9,17 @@ use url::Url;
 #[serde(rename_all = "camelCase")]
 pub struct PullRequest {
     pub id: u16,
+    /// Required to merge the PR, guards against merging a PR that has been updated meanwhile
+    #[serde(default)]
+    pub version: u32,
     pub title: String,
 
     #[serde(rename = "createdDate", with = "ts_milliseconds")]
     pub created: DateTime<Utc>,
     #[serde(rename = "updatedDate", with = "ts_milliseconds")]
     pub updated: DateTime<Utc>,
+    #[serde(rename = "closedDate", with = "ts_milliseconds_option", default)]
+    pub closed: Option<DateTime<Utc>>,
 
     pub author: Actor,
     pub reviewers: Vec<Actor>,
@@ -32,6 +37,22 @@ pub enum PullRequestState {
     Declined,
 }
 
+/// How the user open PRs are filtered by takes part in them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticipantRole {
+    Author,
+    Reviewer,
+}
+
+impl ParticipantRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParticipantRole::Author => "AUTHOR",
+            ParticipantRole::Reviewer => "REVIEWER",
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Ref {
@@ -70,6 +91,7 @@ mod tests {
     fn url() {
         let pr = PullRequest {
             id: 42,
+            version: 0,
             from_ref: Ref {
                 display_id: "_".to_string(),
                 id: "_".to_string(),
@@ -117,6 +139,7 @@ mod tests {
             reviewers: vec![],
             created: Utc::now(),
             updated: Utc::now(),
+            closed: None,
         };
 
         assert_eq!(
@@ -8,6 +8,18 @@ pub struct Repo {
     pub links: RepoLinks,
 }
 
+impl Repo {
+    /// The ssh clone url, or the first one when the server has ssh access disabled.
+    pub fn clone_url(&self) -> Option<&str> {
+        self.links
+            .clone
+            .iter()
+            .find(|link| link.name.as_deref() == Some("ssh"))
+            .or(self.links.clone.first())
+            .map(|link| link.href.as_str())
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RepoLinks {
     pub clone: Vec<Link>,
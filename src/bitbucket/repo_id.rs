@@ -16,6 +16,23 @@ impl RepoId {
             .ok_or(InvalidRepoId)
     }
 
+    /// From `PROJECT/repo`, the path of a page like `projects/PROJECT/repos/repo/browse`
+    /// or of a clone url like `scm/project/repo.git`.
+    pub fn from_path(segments: &[&str]) -> Option<RepoId> {
+        let (project, name) = match segments {
+            ["projects", project, "repos", name, ..] => (project.to_string(), name),
+            // personal repositories belong to the project `~user`
+            ["users", user, "repos", name, ..] => (format!("~{user}"), name),
+            ["scm", project, name] | [project, name] => (project.to_string(), name),
+            _ => return None,
+        };
+
+        Some(RepoId {
+            project,
+            name: String::from(name.trim_end_matches(".git")),
+        })
+    }
+
     fn from_url(url: &str, base_url: &Url) -> Option<RepoId> {
         let url = Url::parse(url).ok()?;
 
@@ -176,6 +193,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_from_path() {
+        let repo_id = |project: &str, name: &str| {
+            Some(RepoId {
+                project: project.to_string(),
+                name: name.to_string(),
+            })
+        };
+
+        assert_eq!(
+            RepoId::from_path(&["AP", "mobile_ios"]),
+            repo_id("AP", "mobile_ios")
+        );
+        assert_eq!(
+            RepoId::from_path(&["projects", "AP", "repos", "mobile_ios", "browse"]),
+            repo_id("AP", "mobile_ios")
+        );
+        assert_eq!(
+            RepoId::from_path(&["users", "me", "repos", "dotfiles"]),
+            repo_id("~me", "dotfiles")
+        );
+        assert_eq!(
+            RepoId::from_path(&["scm", "ap", "mobile_ios.git"]),
+            repo_id("ap", "mobile_ios")
+        );
+        assert_eq!(RepoId::from_path(&["ap", "ios", "mobile_ios"]), None);
+    }
+
     #[test]
     fn url() {
         let repo_id = RepoId {
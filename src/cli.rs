@@ -1,25 +1,48 @@
-mod github_repo_id_parser;
+mod clone_target_parser;
+mod since_parser;
+
+pub use clone_target_parser::CloneTarget;
+
+use std::path::PathBuf;
 
 use clap::{Arg, ArgAction, Command};
 
-use crate::git::Provider;
+use crate::git::{Provider, HOOKS};
+use crate::view::{Column, Format};
 
 pub fn cli(provider: Provider) -> Command {
     Command::new("gitext")
         .subcommand_required(true)
         .arg_required_else_help(true)
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .global(true)
+                .value_name("FORMAT")
+                .value_parser(clap::builder::EnumValueParser::<Format>::new())
+                .default_value("table")
+                .help("Output format of listing commands"),
+        )
         .subcommand(auth())
         .subcommand(browse())
         .subcommand(clone())
         .subcommand(create(provider))
+        .subcommand(credential())
+        .subcommand(hooks())
+        .subcommand(issue())
         .subcommand(pr())
         .subcommand(prs())
+        .subcommand(stats())
         .subcommand(switch())
         .subcommand(ticket())
+        .subcommand(tickets())
 }
 
 fn auth() -> Command {
-    Command::new("auth")
+    Command::new("auth").subcommand(
+        Command::new("setup-git")
+            .about("Make git use gitext's credentials for the configured hosts"),
+    )
 }
 
 fn browse() -> Command {
@@ -29,21 +52,38 @@ fn browse() -> Command {
 }
 
 fn clone() -> Command {
-    Command::new("clone").arg(
-        Arg::new("repo")
-            .required(true)
-            .value_name("ORGANIZATION/REPO")
-            .value_parser(github_repo_id_parser::GithubRepoIdParser),
-    )
+    Command::new("clone")
+        .arg(
+            Arg::new("repo")
+                .required(true)
+                .value_name("REPO")
+                .value_parser(clone_target_parser::CloneTargetParser)
+                .help("ORGANIZATION/REPO, GROUP/SUBGROUP/REPO, PROJECT/REPO or the url of the repository"),
+        )
+        .arg(
+            Arg::new("provider")
+                .long("provider")
+                .value_name("PROVIDER")
+                .value_parser(["github", "gitlab", "bitbucket"])
+                .help("Defaults to gitext.host.<host>.provider of the host"),
+        )
+        .arg(
+            Arg::new("host")
+                .long("host")
+                .value_name("HOST")
+                .help("Defaults to the host of the url or the only host of the provider"),
+        )
 }
 
 fn create(provider: Provider) -> Command {
     match provider {
-        Provider::BitBucket => Command::new("create").arg(
-            Arg::new("project")
-                .required(true)
-                .value_name("PROJECT CODE"),
-        ),
+        Provider::BitBucket => Command::new("create")
+            .arg(
+                Arg::new("project")
+                    .required(true)
+                    .value_name("PROJECT CODE"),
+            )
+            .arg(push()),
         Provider::GitLab => Command::new("create").arg(
             Arg::new("project")
                 .required(true)
@@ -63,34 +103,277 @@ fn create(provider: Provider) -> Command {
                     .long("remote-name")
                     .value_name("REMOTE NAME")
                     .default_value("origin"),
-            ),
+            )
+            .arg(push()),
     }
 }
 
+fn credential() -> Command {
+    Command::new("credential")
+        .about("Git credential helper, see `auth setup-git`")
+        .subcommand_required(true)
+        .subcommand(Command::new("get"))
+        .subcommand(Command::new("store"))
+        .subcommand(Command::new("erase"))
+}
+
 fn pr() -> Command {
     Command::new("pr")
         .subcommand(Command::new("browse").alias("b").arg(id(true)))
-        .subcommand(Command::new("checkout").alias("co").arg(id(true)))
-        .subcommand(Command::new("info").alias("i").arg(id(false)))
+        .subcommand(
+            Command::new("checkout")
+                .alias("co")
+                .arg(id(true))
+                .arg(autostash())
+                .arg(prune())
+                .arg(
+                    Arg::new("worktree")
+                        .short('w')
+                        .long("worktree")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("autostash")
+                        .help("Check out into a worktree at `gitext.pr.worktreePath` instead"),
+                ),
+        )
+        .subcommand(
+            Command::new("info")
+                .alias("i")
+                .arg(id(false))
+                .args(layout()),
+        )
+        .subcommand(
+            Command::new("pick")
+                .alias("p")
+                .arg(Arg::new("filter").required(false).value_name("USERNAME"))
+                .arg(autostash())
+                .arg(prune()),
+        )
         .subcommand(
             Command::new("new")
                 .alias("n")
                 .arg(Arg::new("target").required(true).value_name("BRANCH")),
         )
+        .subcommand(
+            Command::new("create")
+                .alias("c")
+                .arg(Arg::new("target").required(true).value_name("BRANCH"))
+                .arg(
+                    Arg::new("title")
+                        .short('t')
+                        .long("title")
+                        .value_name("TITLE")
+                        .help("Defaults to the summary of the last commit"),
+                )
+                .arg(
+                    Arg::new("draft")
+                        .short('d')
+                        .long("draft")
+                        .action(ArgAction::SetTrue)
+                        .help("Create a draft PR"),
+                )
+                .arg(
+                    Arg::new("no-push")
+                        .long("no-push")
+                        .action(ArgAction::SetTrue)
+                        .help("Don't push the branch before creating the PR"),
+                ),
+        )
+        .subcommand(
+            Command::new("sync")
+                .about("Push the current branch to update its PR")
+                .arg(
+                    Arg::new("force")
+                        .short('f')
+                        .long("force")
+                        .action(ArgAction::SetTrue)
+                        .help("Overwrite a rewritten remote branch, unless it changed since the last fetch"),
+                ),
+        )
+        .subcommand(Command::new("merge").alias("m").arg(id(false)))
+        .subcommand(
+            Command::new("worktrees")
+                .alias("wt")
+                .about("List the worktrees added by `pr checkout --worktree`")
+                .subcommand(
+                    Command::new("prune").about("Remove the worktrees of closed and merged PRs"),
+                ),
+        )
         .subcommand(Command::new("new-or-browse").hide(true))
 }
 
 fn prs() -> Command {
     // TODO: specify `my` and `assigned`
-    Command::new("prs").arg(Arg::new("filter").required(false).value_name("USERNAME"))
+    Command::new("prs")
+        .arg(Arg::new("filter").required(false).value_name("USERNAME"))
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .action(ArgAction::SetTrue)
+                .help("Show open PRs of all repositories in the workspace"),
+        )
+        .arg(
+            Arg::new("workspace")
+                .long("workspace")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("all")
+                .help("Scan the directory for repositories instead of the configured ones"),
+        )
+        .arg(
+            Arg::new("group")
+                .long("group")
+                .value_name("GROUP")
+                .requires("all")
+                .conflicts_with("workspace")
+                .help("Use repositories from `gitext.workspace.<GROUP>.repo`"),
+        )
+        .args(layout())
+}
+
+fn stats() -> Command {
+    Command::new("stats").subcommand_required(true).subcommand(
+        Command::new("prs").arg(
+            Arg::new("since")
+                .long("since")
+                .value_name("PERIOD")
+                .value_parser(since_parser::SinceParser)
+                .default_value("30d")
+                .help("Only count PRs closed after this, e.g. '2w' or '2024-10-01'"),
+        ),
+    )
 }
 
 fn switch() -> Command {
-    Command::new("switch").arg(Arg::new("id").required(true).value_name("PR id"))
+    Command::new("switch")
+        .arg(Arg::new("id").required(false).value_name("PR id"))
+        .arg(autostash())
+        .arg(prune())
+}
+
+fn hooks() -> Command {
+    Command::new("hooks")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("install")
+                .about("Install hooks that put the ticket key into commit messages"),
+        )
+        .subcommand(
+            Command::new("run")
+                .hide(true)
+                .arg(Arg::new("hook").required(true).value_parser(
+                    clap::builder::PossibleValuesParser::new(HOOKS.iter().copied()),
+                ))
+                .arg(
+                    Arg::new("args")
+                        .num_args(0..)
+                        .trailing_var_arg(true)
+                        .allow_hyphen_values(true),
+                ),
+        )
+}
+
+fn issue() -> Command {
+    Command::new("issue")
+        .subcommand(Command::new("list").alias("ls"))
+        .subcommand(Command::new("view").alias("v").arg(key()))
+        .subcommand(
+            Command::new("create")
+                .alias("c")
+                .arg(Arg::new("title").required(true).value_name("TITLE"))
+                .arg(
+                    Arg::new("body")
+                        .long("body")
+                        .short('b')
+                        .value_name("TEXT")
+                        .help("Description of the issue"),
+                )
+                .arg(
+                    Arg::new("project")
+                        .long("project")
+                        .value_name("PROJECT")
+                        .help("Jira project, defaults to the only one in `gitext.ticketProjects`"),
+                )
+                .arg(
+                    Arg::new("type")
+                        .long("type")
+                        .value_name("TYPE")
+                        .default_value("Task")
+                        .help("Jira issue type"),
+                ),
+        )
+        .subcommand(Command::new("close").arg(key()))
+        .subcommand(Command::new("browse").alias("b").arg(key()))
 }
 
 fn ticket() -> Command {
     Command::new("ticket")
+        .subcommand(Command::new("browse").alias("b").arg(key()))
+        .subcommand(Command::new("info").alias("i").arg(key()))
+        .subcommand(Command::new("auth"))
+        .subcommand(
+            Command::new("start")
+                .alias("s")
+                .arg(Arg::new("key").required(true).value_name("KEY"))
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("BRANCH")
+                        .help("Branch to start from, overrides `gitext.ticket.baseBranch`"),
+                )
+                .arg(
+                    Arg::new("assign")
+                        .short('a')
+                        .long("assign")
+                        .action(ArgAction::SetTrue)
+                        .help("Assign the ticket to yourself"),
+                )
+                .arg(autostash())
+                .arg(prune())
+                .arg(
+                    Arg::new("no-transition")
+                        .long("no-transition")
+                        .action(ArgAction::SetTrue)
+                        .help("Don't move the ticket to `gitext.jira.onStart`"),
+                ),
+        )
+        .subcommand(Command::new("transitions").arg(key()))
+        .subcommand(
+            Command::new("transition").alias("t").arg(
+                Arg::new("transition")
+                    .required(true)
+                    .num_args(1..=2)
+                    .value_names(["KEY", "STATUS"])
+                    .help(
+                        "Target status or transition name, the key defaults to the current branch",
+                    ),
+            ),
+        )
+}
+
+fn tickets() -> Command {
+    Command::new("tickets").arg(
+        Arg::new("jql")
+            .long("jql")
+            .value_name("JQL")
+            .help("Query to run instead of `gitext.tickets.jql`"),
+    )
+}
+
+fn layout() -> [Arg; 2] {
+    [
+        Arg::new("columns")
+            .long("columns")
+            .value_name("COLUMNS")
+            .value_delimiter(',')
+            .value_parser(clap::builder::EnumValueParser::<Column>::new())
+            .help("Comma-separated list of columns to show, overrides `gitext.prs.columns`"),
+        Arg::new("template")
+            .long("template")
+            .value_name("TEMPLATE")
+            .conflicts_with("columns")
+            .help("Print each PR on its own line, e.g. '{id}\\t{title}'"),
+    ]
 }
 
 fn id(required: bool) -> Arg {
@@ -99,3 +382,31 @@ fn id(required: bool) -> Arg {
         .value_name("PR id")
         .value_parser(clap::value_parser!(u16))
 }
+
+fn autostash() -> Arg {
+    Arg::new("autostash")
+        .long("autostash")
+        .action(ArgAction::SetTrue)
+        .help("Stash uncommitted changes before switching, like `gitext.autostash`")
+}
+
+fn push() -> Arg {
+    Arg::new("push")
+        .long("push")
+        .action(ArgAction::SetTrue)
+        .help("Push the current branch to the new remote and track it")
+}
+
+fn prune() -> Arg {
+    Arg::new("prune")
+        .long("prune")
+        .action(ArgAction::SetTrue)
+        .help("Fetch all branches and remove the ones deleted from the remote, like `fetch.prune`")
+}
+
+fn key() -> Arg {
+    Arg::new("key")
+        .required(false)
+        .value_name("KEY")
+        .help("Jira ticket or issue number, defaults to the one in the current branch name")
+}
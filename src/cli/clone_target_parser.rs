@@ -0,0 +1,170 @@
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::Path;
+
+use clap::{
+    builder::TypedValueParser,
+    error::{ContextKind, ContextValue, ErrorKind},
+    Arg, Command, Error,
+};
+use url::Url;
+
+/// The repository `clone` was asked for, which provider it belongs to is decided later.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CloneTarget {
+    /// `ORGANIZATION/REPO`, `GROUP/SUBGROUP/REPO` or `PROJECT/REPO`
+    Path(Vec<String>),
+    /// The url of a page or the clone url of the repository
+    Url(Url),
+}
+
+impl CloneTarget {
+    pub fn host(&self) -> Option<&str> {
+        match self {
+            CloneTarget::Path(_) => None,
+            CloneTarget::Url(url) => url.host_str(),
+        }
+    }
+
+    /// Path segments of the repository below `base_url`, `None` for urls of other hosts.
+    pub fn segments(&self, base_url: &Url) -> Option<Vec<&str>> {
+        match self {
+            CloneTarget::Path(segments) => Some(segments.iter().map(String::as_str).collect()),
+            CloneTarget::Url(url) => {
+                if url.host_str() != base_url.host_str() {
+                    return None;
+                }
+
+                let base_path = base_url.path().trim_end_matches('/');
+                let path = url.path().strip_prefix(base_path)?;
+
+                Some(
+                    path.split('/')
+                        .filter(|segment| !segment.is_empty())
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for CloneTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneTarget::Path(segments) => write!(f, "{}", segments.join("/")),
+            CloneTarget::Url(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CloneTargetParser;
+impl TypedValueParser for CloneTargetParser {
+    type Value = CloneTarget;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or(Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        parse_clone_target(value).ok_or_else(|| {
+            let mut error = Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+
+            error.insert(
+                ContextKind::InvalidArg,
+                ContextValue::String(arg.map(ToString::to_string).unwrap_or("...".to_string())),
+            );
+
+            error.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(value.to_owned()),
+            );
+
+            error
+        })
+    }
+}
+
+fn parse_clone_target(value: &str) -> Option<CloneTarget> {
+    // local repositories are git's to clone
+    if value.starts_with(['.', '/', '~']) || Path::new(value).exists() {
+        return None;
+    }
+
+    if value.contains("://") {
+        return Url::parse(value)
+            .ok()
+            .filter(|url| url.host_str().is_some())
+            .map(CloneTarget::Url);
+    }
+
+    // scp-like syntax of ssh urls, `git@gitlab.company.com:group/repo.git`
+    if let Some((server, path)) = value.split_once(':') {
+        if server.contains('/') {
+            return None;
+        }
+
+        return Url::parse(&format!("ssh://{server}/{path}"))
+            .ok()
+            .map(CloneTarget::Url);
+    }
+
+    let segments = value
+        .trim_matches('/')
+        .split('/')
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if segments.len() < 2 || segments.iter().any(String::is_empty) {
+        return None;
+    }
+
+    Some(CloneTarget::Path(segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets() {
+        assert_eq!(
+            parse_clone_target("mobile/ios/app"),
+            Some(CloneTarget::Path(vec![
+                "mobile".to_string(),
+                "ios".to_string(),
+                "app".to_string()
+            ]))
+        );
+        assert_eq!(parse_clone_target("app"), None);
+        assert_eq!(parse_clone_target("mobile//app"), None);
+        assert_eq!(parse_clone_target("../mobile/app"), None);
+        assert_eq!(parse_clone_target("/mobile/app"), None);
+        assert_eq!(parse_clone_target("~/mobile/app"), None);
+        assert_eq!(parse_clone_target("src/cli"), None);
+
+        let base_url = Url::parse("https://company.com/gitlab/").unwrap();
+
+        let web = parse_clone_target("https://company.com/gitlab/mobile/app/-/tree/main").unwrap();
+        assert_eq!(web.host(), Some("company.com"));
+        assert_eq!(
+            web.segments(&base_url),
+            Some(vec!["mobile", "app", "-", "tree", "main"])
+        );
+
+        let scp = parse_clone_target("git@company.com:mobile/app.git").unwrap();
+        assert_eq!(scp.host(), Some("company.com"));
+        assert_eq!(
+            scp.segments(&Url::parse("https://company.com").unwrap()),
+            Some(vec!["mobile", "app.git"])
+        );
+
+        let other = parse_clone_target("https://github.com/chipp/gitext").unwrap();
+        assert_eq!(other.segments(&base_url), None);
+    }
+}
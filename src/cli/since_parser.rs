@@ -0,0 +1,93 @@
+use std::ffi::OsStr;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::{
+    builder::TypedValueParser,
+    error::{ContextKind, ContextValue, ErrorKind},
+    Arg, Command, Error,
+};
+
+/// Parses either a period relative to now (`12h`, `30d`, `2w`) or a date (`2024-10-01`).
+#[derive(Clone, Debug)]
+pub struct SinceParser;
+impl TypedValueParser for SinceParser {
+    type Value = DateTime<Utc>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let value = value
+            .to_str()
+            .ok_or(Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        parse_since(value, Utc::now()).ok_or_else(|| {
+            let mut error = Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
+
+            error.insert(
+                ContextKind::InvalidArg,
+                ContextValue::String(arg.map(ToString::to_string).unwrap_or("...".to_string())),
+            );
+
+            error.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(value.to_owned()),
+            );
+
+            error
+        })
+    }
+}
+
+fn parse_since(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+
+    let split = value.len().checked_sub(1)?;
+    let (amount, unit) = value.split_at(split);
+    let amount = amount.parse::<i64>().ok()?;
+
+    let period = match unit {
+        "h" => Duration::try_hours(amount)?,
+        "d" => Duration::try_days(amount)?,
+        "w" => Duration::try_weeks(amount)?,
+        _ => return None,
+    };
+
+    now.checked_sub_signed(period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn periods() {
+        let now = Utc.with_ymd_and_hms(2024, 10, 31, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            parse_since("30d", now),
+            Some(Utc.with_ymd_and_hms(2024, 10, 1, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_since("12h", now),
+            Some(Utc.with_ymd_and_hms(2024, 10, 31, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_since("2w", now),
+            Some(Utc.with_ymd_and_hms(2024, 10, 17, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_since("2024-09-01", now),
+            Some(Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap())
+        );
+
+        assert_eq!(parse_since("30", now), None);
+        assert_eq!(parse_since("d", now), None);
+        assert_eq!(parse_since("3m", now), None);
+    }
+}
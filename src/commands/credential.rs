@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use clap::ArgMatches;
+use git2::Config as GitConfig;
+
+use crate::git::{
+    get_config, get_configured_hosts, get_host_config, get_repo, set_host_config, Config, Provider,
+};
+use crate::{Error, Result};
+
+/// `credential` makes gitext a git credential helper, so git uses the credentials of the
+/// auth domain of a host just like the API clients do.
+pub struct Credential;
+
+impl Credential {
+    pub fn handle(args: &ArgMatches, path: &Path) -> Result<()> {
+        let description = parse_description(io::stdin().lock()).map_err(Error::ReadCredential)?;
+
+        // git asks every helper, those of hosts gitext doesn't know just say nothing
+        let config = match Self::config_for(&description, path) {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+
+        match args.subcommand() {
+            Some(("get", _)) => {
+                let (username, password) = match config.provider {
+                    // any username goes with a personal access token
+                    Provider::GitLab => (
+                        "oauth2".to_string(),
+                        chipp_auth::token(&config.auth_domain, "access_token"),
+                    ),
+                    Provider::BitBucket | Provider::GitHub => {
+                        chipp_auth::user_and_password(&config.auth_domain)
+                    }
+                };
+
+                println!("username={username}");
+                println!("password={password}");
+            }
+            Some(("erase", _)) => match config.provider {
+                Provider::GitLab => chipp_auth::reset_token(&config.auth_domain, "access_token"),
+                Provider::BitBucket | Provider::GitHub => {
+                    chipp_auth::reset_user_and_pass(&config.auth_domain)
+                }
+            },
+            // what git stores is what `get` has read from the storage already
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// `auth setup-git`, makes git ask gitext for the credentials of the host of `config`
+    /// and of every host with a `gitext.host.<host>.provider`.
+    pub fn setup_git(config: Option<&Config>) -> Result<()> {
+        let path = match GitConfig::find_global() {
+            Ok(path) => path,
+            Err(_) => dirs::home_dir().unwrap().join(".gitconfig"),
+        };
+        let mut global = GitConfig::open(&path)?;
+
+        if let Some(config) = config {
+            let host = config.base_url.host_str().unwrap_or_default();
+
+            // git asks outside of the repository too, e.g. when cloning
+            if get_host_config(&global, host).is_err() {
+                set_host_config(&mut global, config)?;
+            }
+        }
+
+        let hosts = get_configured_hosts(&global);
+        if hosts.is_empty() {
+            return Err(Error::NoConfiguredHosts);
+        }
+
+        let exe = std::env::current_exe()
+            .map(|exe| exe.display().to_string())
+            .unwrap_or("gitext".to_string());
+        let helper = format!("!{exe} credential");
+
+        for host in hosts {
+            let host_config = get_host_config(&global, &host)?;
+            let key = format!(
+                "credential.{}.helper",
+                host_config.base_url.origin().ascii_serialization()
+            );
+
+            // an empty helper clears the ones configured before, so git asks only gitext
+            let _ = global.remove_multivar(&key, ".*");
+            global.set_multivar(&key, "^$", "")?;
+            global.set_multivar(&key, "^!", &helper)?;
+
+            println!(
+                "git uses the credentials of {} for {host}",
+                host_config.auth_domain
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The repository's config when its host is the one git asks about, otherwise the
+    /// global config of the host.
+    fn config_for(description: &HashMap<String, String>, path: &Path) -> Option<Config> {
+        if !matches!(description.get("protocol")?.as_str(), "http" | "https") {
+            return None;
+        }
+
+        let host = description.get("host")?;
+        // the host comes with the port if the url has one
+        let host = host.split(':').next().unwrap_or(host);
+
+        let repo_config = get_repo(path)
+            .ok()
+            .and_then(|repo| get_config(&repo).ok())
+            .filter(|config| config.base_url.host_str() == Some(host));
+
+        repo_config.or_else(|| {
+            let global = GitConfig::open_default().ok()?;
+
+            match get_host_config(&global, host) {
+                Ok(config) => Some(config),
+                Err(_) if host == "github.com" => Some(Config::default()),
+                Err(_) => None,
+            }
+        })
+    }
+}
+
+/// Reads the `key=value` lines git sends to credential helpers, up to an empty line.
+fn parse_description(input: impl BufRead) -> io::Result<HashMap<String, String>> {
+    let mut description = HashMap::new();
+
+    for line in input.lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            description.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn description() {
+        let input = "protocol=https\nhost=git.company.com:8443\nusername=me\n\nignored=1\n";
+        let description = parse_description(input.as_bytes()).unwrap();
+
+        assert_eq!(description.len(), 3);
+        assert_eq!(description["host"], "git.company.com:8443");
+        assert_eq!(description["username"], "me");
+    }
+}
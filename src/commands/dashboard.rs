@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use crate::git::{
+    get_config, get_prs_columns_from_config, get_workspace_from_config, Config, Provider,
+};
+use crate::view::{Column, Format, Output, PullRequestView};
+use crate::{gighub, gitbucket, gitlad};
+use crate::{Error, Result};
+
+use clap::ArgMatches;
+use futures::future::join_all;
+use git2::{Config as GitConfig, Repository};
+
+pub struct Dashboard;
+
+impl Dashboard {
+    pub async fn handle(args: &ArgMatches, repo: Option<&Repository>, path: &Path) -> Result<()> {
+        let config = match repo {
+            Some(repo) => repo.config()?,
+            None => GitConfig::open_default()?,
+        };
+
+        let columns = get_prs_columns_from_config(&config);
+        let paths = Self::repo_paths(args, repo, &config, path)?;
+
+        let repos = paths
+            .into_iter()
+            .filter_map(|path| {
+                let repo = Repository::open(&path).ok()?;
+                let config = get_config(&repo).ok()?;
+                let name = path.file_name()?.to_string_lossy().to_string();
+
+                Some((name, repo, config))
+            })
+            .collect::<Vec<_>>();
+
+        let output = Output::from_args(args, columns.as_deref(), Self::default_columns())?;
+
+        if repos.is_empty() && output.format() == Format::Table {
+            println!("No gitext repositories found");
+            return Ok(());
+        }
+
+        let results = join_all(repos.iter().map(|(_, repo, config)| {
+            Self::collect(args, repo, config, output.selects(Column::Merge))
+        }))
+        .await;
+
+        let results = repos
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .zip(results)
+            .collect::<Vec<_>>();
+
+        output.print_grouped(results);
+
+        Ok(())
+    }
+
+    async fn collect(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Config,
+        mergeability: bool,
+    ) -> Result<Vec<PullRequestView>> {
+        match config.provider {
+            // a request for each PR, only when the merge column is asked for
+            Provider::BitBucket => gitbucket::Prs::collect(args, repo, config, mergeability).await,
+            Provider::GitLab => gitlad::Prs::collect(args, repo, config).await,
+            Provider::GitHub => gighub::Prs::collect(args, repo, config, mergeability).await,
+        }
+    }
+
+    fn repo_paths(
+        args: &ArgMatches,
+        repo: Option<&Repository>,
+        config: &GitConfig,
+        path: &Path,
+    ) -> Result<Vec<PathBuf>> {
+        if let Some(dir) = args.get_one::<PathBuf>("workspace") {
+            return Self::scan(dir);
+        }
+
+        let group = args.get_one::<String>("group").map(String::as_str);
+        let (repos, dir) = get_workspace_from_config(config, group);
+
+        if !repos.is_empty() {
+            return Ok(repos);
+        }
+
+        let dir = match (dir, group) {
+            (Some(dir), _) => dir,
+            (None, Some(group)) => {
+                return Err(Error::WorkspaceGroupNotConfigured(group.to_string()))
+            }
+            (None, None) => repo
+                .and_then(Repository::workdir)
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| path.to_path_buf()),
+        };
+
+        Self::scan(&dir)
+    }
+
+    fn scan(dir: &Path) -> Result<Vec<PathBuf>> {
+        let entries =
+            std::fs::read_dir(dir).map_err(|err| Error::ReadWorkspace(dir.to_path_buf(), err))?;
+
+        let mut paths = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        Ok(paths)
+    }
+
+    fn default_columns() -> Vec<Column> {
+        vec![
+            Column::Id,
+            Column::Author,
+            Column::Title,
+            Column::Ci,
+            Column::Approvals,
+            Column::Merge,
+            Column::Target,
+            Column::Updated,
+            Column::Jira,
+        ]
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git::{
+    chained_hook_path, get_current_branch, get_hooks_config, get_hooks_dir, hook_script,
+    is_gitext_hook, is_only_prefix, message_subject, prefix_message, CommitHooksConfig,
+    TicketPatternConfig, HOOKS,
+};
+use crate::{Error, Result};
+
+use clap::ArgMatches;
+use git2::{Repository, RepositoryState};
+
+pub struct Hooks;
+
+impl Hooks {
+    pub fn handle(args: &ArgMatches, repo: &Repository) -> Result<()> {
+        match args.subcommand() {
+            Some(("install", _)) => Self::install(repo),
+            Some(("run", args)) => match get_hooks_config(repo) {
+                Some(config) => Self::run(args, repo, &config),
+                // a global `core.hooksPath` runs the hooks in repositories gitext doesn't handle
+                None => Ok(()),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn install(repo: &Repository) -> Result<()> {
+        let dir = get_hooks_dir(repo);
+        fs::create_dir_all(&dir).map_err(|err| Error::WriteHook(dir.clone(), err))?;
+
+        let gitext = std::env::current_exe().map_err(|err| Error::WriteHook(dir.clone(), err))?;
+
+        for name in HOOKS {
+            let path = dir.join(name);
+
+            if path.exists() {
+                let contents =
+                    fs::read(&path).map_err(|err| Error::WriteHook(path.clone(), err))?;
+
+                // reinstalling only updates the path to gitext
+                if !is_gitext_hook(&String::from_utf8_lossy(&contents)) {
+                    let chained = chained_hook_path(&path);
+
+                    if chained.exists() {
+                        return Err(Error::ChainedHookExists(chained));
+                    }
+
+                    fs::rename(&path, &chained)
+                        .map_err(|err| Error::WriteHook(chained.clone(), err))?;
+                    println!("Moved the existing {name} hook to {}", chained.display());
+                }
+            }
+
+            Self::write_hook(&path, &hook_script(name, &gitext))?;
+            println!("Installed {}", path.display());
+        }
+
+        Ok(())
+    }
+
+    fn write_hook(path: &Path, script: &str) -> Result<()> {
+        fs::write(path, script).map_err(|err| Error::WriteHook(path.to_path_buf(), err))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+                .map_err(|err| Error::WriteHook(path.to_path_buf(), err))?;
+        }
+
+        Ok(())
+    }
+
+    fn run<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<()>
+    where
+        Conf: CommitHooksConfig,
+        Conf: TicketPatternConfig,
+    {
+        let hook = args.get_one::<String>("hook").expect("required");
+        let params = args
+            .get_many::<String>("args")
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        let file = match params.first() {
+            Some(file) => PathBuf::from(file),
+            None => return Ok(()),
+        };
+
+        // rebases and other detached commits are left alone
+        let branch = match get_current_branch(repo) {
+            Some(branch) => branch,
+            None => return Ok(()),
+        };
+
+        let pattern = config.ticket_pattern();
+        let key = match pattern.find(&branch) {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let key = key.as_str();
+
+        let prefix = config.commit_prefix().replace("{key}", key);
+        let message =
+            fs::read_to_string(&file).map_err(|err| Error::CommitMessage(file.clone(), err))?;
+
+        match hook.as_str() {
+            "prepare-commit-msg" => {
+                // merges, squashes and amends already have a message
+                if let Some("merge" | "squash" | "commit") = params.get(1).copied() {
+                    return Ok(());
+                }
+
+                if let Some(message) = prefix_message(&message, &prefix, key, pattern) {
+                    fs::write(&file, message).map_err(|err| Error::CommitMessage(file, err))?;
+                }
+            }
+            "commit-msg" => {
+                if repo.state() != RepositoryState::Clean {
+                    return Ok(());
+                }
+
+                // `prepare-commit-msg` made the message non-empty, git won't abort by itself
+                if is_only_prefix(&message, &prefix) {
+                    return Err(Error::EmptyCommitMessage);
+                }
+
+                let has_key = message_subject(&message)
+                    .is_some_and(|subject| pattern.references(subject, key));
+
+                if config.commit_require_key() && !has_key {
+                    return Err(Error::NoTicketInCommit(key.to_string()));
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+}
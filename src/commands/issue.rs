@@ -0,0 +1,262 @@
+use crate::git::{
+    get_current_branch, BaseUrlConfig, Config, JiraTransitionsConfig, JiraUrlConfig, Provider,
+    TicketPattern, TicketPatternConfig, TicketsJqlConfig, Tracker,
+};
+use crate::jira::{self, JiraTracker};
+use crate::tracker::{IssueTracker, TrackedIssue};
+use crate::view::{self, Format, IssueRowView, IssueView, PullRequestView};
+use crate::{gighub, gitbucket, github, gitlab, gitlad};
+use crate::{Error, Result};
+
+use clap::ArgMatches;
+use git2::Repository;
+use std::process::{Command, Stdio};
+
+pub struct Issue;
+
+impl Issue {
+    pub async fn handle(args: &ArgMatches, repo: &Repository, config: &Config) -> Result<()> {
+        match (config.tracker, config.provider) {
+            (Tracker::Jira, _) => {
+                let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+                let project = Self::jira_project(args, config.ticket_pattern());
+
+                let tracker = JiraTracker {
+                    client: jira::Client::new(jira_url, config.jira_auth()),
+                    jira_url,
+                    jql: config.tickets_jql(),
+                    project: project.as_deref(),
+                    issue_type: Self::jira_issue_type(args),
+                    close_status: config.jira_on_close(),
+                };
+
+                Self::run(args, repo, config, &tracker).await
+            }
+            (Tracker::Issues, Provider::GitHub) => {
+                let repo_id =
+                    github::get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+                let tracker = github::GitHubTracker {
+                    client: github::Client::new(config),
+                    repo_id: &repo_id,
+                    base_url: config.base_url(),
+                };
+
+                Self::run(args, repo, config, &tracker).await
+            }
+            (Tracker::Issues, Provider::GitLab) => {
+                let repo_id =
+                    gitlab::get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+                let tracker = gitlab::GitLabTracker {
+                    client: gitlab::Client::new(config),
+                    repo_id: &repo_id,
+                    base_url: config.base_url(),
+                };
+
+                Self::run(args, repo, config, &tracker).await
+            }
+            // rejected when the config is read
+            (Tracker::Issues, Provider::BitBucket) => unreachable!(),
+        }
+    }
+
+    async fn run<T>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Config,
+        tracker: &T,
+    ) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        match args.subcommand() {
+            Some(("list", args)) => Self::list(args, tracker).await,
+            Some(("view" | "info", args)) => Self::view(args, repo, config, tracker).await,
+            Some(("create", args)) => Self::create(args, tracker).await,
+            Some(("close", args)) => Self::close(args, repo, config, tracker).await,
+            Some(("browse", args)) => Self::browse(args, repo, config, tracker),
+            // `ticket` subcommands that only make sense for Jira
+            Some((command, _)) => Err(Error::NotSupportedByTracker(format!("ticket {command}"))),
+            None => Self::browse(args, repo, config, tracker),
+        }
+    }
+
+    async fn list<T>(args: &ArgMatches, tracker: &T) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let views = tracker
+            .list()
+            .await?
+            .into_iter()
+            .map(|issue| IssueRowView {
+                key: issue.key,
+                status: issue.state,
+                assignee: issue.assignee,
+                summary: issue.title,
+            })
+            .collect::<Vec<_>>();
+
+        if views.is_empty() && format == Format::Table {
+            println!("No issues found");
+            return Ok(());
+        }
+
+        view::print(&views, format);
+
+        Ok(())
+    }
+
+    async fn view<T>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Config,
+        tracker: &T,
+    ) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let issue = tracker.get(&key).await?;
+
+        // branches and titles reference issues without the `#`
+        let reference = issue.key.trim_start_matches('#');
+
+        // the issue is still worth showing when the PRs can't be found
+        let prs = Self::find_prs(reference, repo, config)
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!("warning: can't find PRs for {}: {err}", issue.key);
+                vec![]
+            });
+
+        view::print_issue(&Self::issue_view(issue, prs), format);
+
+        Ok(())
+    }
+
+    async fn create<T>(args: &ArgMatches, tracker: &T) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        let title = args.get_one::<String>("title").expect("required");
+        let body = args.get_one::<String>("body").map(String::as_str);
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let issue = tracker.create(title, body).await?;
+
+        match format {
+            Format::Table => println!("Created {}: {}", issue.key, issue.url),
+            format => view::print_one(&Self::issue_view(issue, vec![]), format),
+        }
+
+        Ok(())
+    }
+
+    async fn close<T>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Config,
+        tracker: &T,
+    ) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let issue = tracker.close(&key).await?;
+
+        println!("Closed {}, it's {} now", issue.key, issue.state);
+
+        Ok(())
+    }
+
+    fn browse<T>(args: &ArgMatches, repo: &Repository, config: &Config, tracker: &T) -> Result<()>
+    where
+        T: IssueTracker,
+    {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let url = tracker.url(&key);
+
+        Command::new("open")
+            .arg(url.as_str())
+            .stdout(Stdio::null())
+            .spawn()
+            .map(|_| ())
+            .map_err(|err| Error::OpenUrl(err, url))?;
+
+        Ok(())
+    }
+
+    fn issue_view(issue: TrackedIssue, prs: Vec<PullRequestView>) -> IssueView {
+        IssueView {
+            key: issue.key,
+            url: issue.url,
+            summary: issue.title,
+            status: issue.state,
+            assignee: issue.assignee,
+            priority: None,
+            sprint: None,
+            description: issue.description,
+            prs,
+        }
+    }
+
+    async fn find_prs(
+        key: &str,
+        repo: &Repository,
+        config: &Config,
+    ) -> Result<Vec<PullRequestView>> {
+        match config.provider {
+            Provider::BitBucket => gitbucket::Prs::find_for_ticket(key, repo, config).await,
+            Provider::GitLab => gitlad::Prs::find_for_ticket(key, repo, config).await,
+            Provider::GitHub => gighub::Prs::find_for_ticket(key, repo, config).await,
+        }
+    }
+
+    /// `--project`, or the project of the allowlist when there's only one.
+    fn jira_project(args: &ArgMatches, pattern: &TicketPattern) -> Option<String> {
+        let project = args
+            .subcommand_matches("create")
+            .and_then(|args| args.get_one::<String>("project"));
+
+        if let Some(project) = project {
+            return Some(project.clone());
+        }
+
+        match pattern.projects() {
+            [project] => Some(project.to_ascii_uppercase()),
+            _ => None,
+        }
+    }
+
+    fn jira_issue_type(args: &ArgMatches) -> &str {
+        args.subcommand_matches("create")
+            .and_then(|args| args.get_one::<String>("type"))
+            .map(String::as_str)
+            .unwrap_or("Task")
+    }
+
+    fn key(args: &ArgMatches, repo: &Repository, pattern: &TicketPattern) -> Result<String> {
+        if let Some(key) = args.try_get_one::<String>("key").ok().flatten() {
+            return Ok(key.clone());
+        }
+
+        let branch = get_current_branch(repo).ok_or(Error::Detached)?;
+
+        pattern
+            .find_for_branch(&branch, None, Some(repo))
+            .ok_or(Error::NoJiraTicket(branch.to_string()))
+    }
+}
@@ -1,32 +1,250 @@
-use crate::git::{extract_ticket, get_current_branch, BaseUrlConfig, JiraUrlConfig};
+use crate::git::{
+    branch_name_for_ticket, fetch_remote, find_remote_by_priority, get_current_branch,
+    get_remote_default_branch, switch_with_autostash, AuthDomainConfig, AutostashConfig, Config,
+    FetchSpec, JiraAuth, JiraTransitionsConfig, JiraUrlConfig, Provider, TicketPattern,
+    TicketPatternConfig, TicketStartConfig,
+};
+use crate::jira::{self, Client};
+use crate::view::{self, Format, IssueView, PullRequestView, TransitionView, UserView};
+use crate::{gighub, gitbucket, gitlad};
 use crate::{Error, Result};
 
-use git2::Repository;
+use clap::ArgMatches;
+use git2::{BranchType, Repository};
 use std::process::{Command, Stdio};
 
 pub struct Ticket;
 
 impl Ticket {
-    pub fn handle<Conf>(repo: Repository, config: Conf) -> Result<()>
+    pub async fn handle(args: &ArgMatches, repo: &Repository, config: &Config) -> Result<()> {
+        match args.subcommand() {
+            Some(("auth", args)) => Self::auth(args, config).await,
+            Some(("info", args)) => Self::info(args, repo, config).await,
+            Some(("browse", args)) => Self::browse(args, repo, config),
+            Some(("start", args)) => Self::start(args, repo, config).await,
+            Some(("transitions", args)) => Self::transitions(args, repo, config).await,
+            Some(("transition", args)) => Self::transition(args, repo, config).await,
+            _ => Self::browse(args, repo, config),
+        }
+    }
+
+    async fn auth<Conf>(args: &ArgMatches, config: &Conf) -> Result<()>
     where
-        Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
     {
-        let branch = get_current_branch(&repo).ok_or(Error::Detached)?;
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+        let auth_domain = jira_url.host_str().unwrap_or_default();
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
 
-        let ticket = extract_ticket(&branch).ok_or(Error::NoJiraTicket(branch.to_string()))?;
+        let user = match Client::new(jira_url, config.jira_auth()).whoami().await {
+            Ok(user) => user,
+            Err(_) => {
+                // forget the stored credentials, the next request asks for new ones
+                match config.jira_auth() {
+                    JiraAuth::Token => chipp_auth::reset_token(auth_domain, "access_token"),
+                    JiraAuth::Basic => chipp_auth::reset_user_and_pass(auth_domain),
+                }
 
-        let mut url = config
-            .jira_url()
-            .cloned()
-            .ok_or(Error::JiraUrlNotConfigured)?;
+                Client::new(jira_url, config.jira_auth())
+                    .whoami()
+                    .await
+                    .map_err(|_| Error::AuthorizationError)?
+            }
+        };
 
-        {
-            let mut segments = url.path_segments_mut().unwrap();
-            segments.push("browse");
-            segments.push(ticket);
+        match format {
+            Format::Table => println!("You're logged in to {auth_domain} as {}", user.display_name),
+            format => {
+                let user = UserView {
+                    login: user.name.or(user.account_id).unwrap_or_default(),
+                    name: Some(user.display_name),
+                };
+                view::print_one(&user, format);
+            }
         }
 
+        Ok(())
+    }
+
+    async fn info(args: &ArgMatches, repo: &Repository, config: &Config) -> Result<()> {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let client = Client::new(jira_url, config.jira_auth());
+        let issue = client.get_issue(&key).await?;
+
+        // the ticket is still worth showing when the provider can't be reached
+        let prs = Self::find_prs(&key, repo, config)
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!("warning: can't find PRs for {key}: {err}");
+                vec![]
+            });
+
+        let view = IssueView {
+            url: jira::browse_url(jira_url, &issue.key),
+            sprint: issue.sprint(),
+            key: issue.key,
+            summary: issue.fields.summary,
+            status: issue.fields.status.name,
+            assignee: issue.fields.assignee.map(|user| user.display_name),
+            priority: issue.fields.priority.map(|priority| priority.name),
+            description: issue.fields.description,
+            prs,
+        };
+
+        view::print_issue(&view, format);
+
+        Ok(())
+    }
+
+    async fn start<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<()>
+    where
+        Conf: AuthDomainConfig,
+        Conf: AutostashConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: JiraTransitionsConfig,
+        Conf: TicketStartConfig,
+    {
+        let key = args.get_one::<String>("key").expect("required");
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+
+        let client = Client::new(jira_url, config.jira_auth());
+        let issue = client.get_issue(key).await?;
+
+        let branch = branch_name_for_ticket(
+            config.ticket_branch_template(),
+            &issue.key,
+            &issue.fields.summary,
+        );
+
+        let mut remote = find_remote_by_priority(repo, Some).ok_or(Error::NoRemote)?;
+        let fetch = FetchSpec {
+            branch: None,
+            prune: args.get_flag("prune"),
+        };
+        fetch_remote(&mut remote, fetch, repo, config)?;
+
+        let base = match args.get_one::<String>("base") {
+            Some(base) => base.clone(),
+            None => config
+                .ticket_base_branch()
+                .map(String::from)
+                .or_else(|| get_remote_default_branch(&remote, repo))
+                .unwrap_or("main".to_string()),
+        };
+
+        let base = format!("{}/{}", remote.name().unwrap_or_default(), base);
+        let commit = repo
+            .find_branch(&base, BranchType::Remote)?
+            .get()
+            .peel_to_commit()?
+            .id()
+            .to_string();
+
+        println!("{}: {}", issue.key, issue.fields.summary);
+        let autostash = args.get_flag("autostash") || config.autostash();
+        switch_with_autostash(&branch, &commit, &remote, repo, autostash)?;
+
+        if args.get_flag("assign") || config.ticket_assign_on_start() {
+            let assigned = match client.whoami().await {
+                Ok(user) => client.assign_issue(&issue.key, &user).await,
+                Err(err) => Err(err),
+            };
+
+            match assigned {
+                Ok(()) => println!("Assigned {} to you", issue.key),
+                Err(err) => eprintln!("warning: can't assign {}: {err}", issue.key),
+            }
+        }
+
+        let status = config
+            .jira_on_start()
+            .filter(|_| !args.get_flag("no-transition"));
+
+        if let Some(status) = status {
+            match jira::transition_ticket(&client, &issue.key, status).await {
+                Ok(transition) => println!("Moved {} to {}", issue.key, transition.to.name),
+                Err(err) => eprintln!("warning: can't move {} to `{status}`: {err}", issue.key),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn transitions<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<()>
+    where
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+    {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let client = Client::new(jira_url, config.jira_auth());
+        let transitions = client.get_transitions(&key).await?;
+
+        let views = transitions
+            .into_iter()
+            .map(|transition| TransitionView {
+                id: transition.id,
+                name: transition.name,
+                to: transition.to.name,
+            })
+            .collect::<Vec<_>>();
+
+        view::print(&views, format);
+
+        Ok(())
+    }
+
+    async fn transition<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<()>
+    where
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+    {
+        let mut values = args
+            .get_many::<String>("transition")
+            .expect("required")
+            .collect::<Vec<_>>();
+
+        // the key is optional and comes first
+        let status = values.pop().expect("required");
+        let key = match values.pop() {
+            Some(key) => key.clone(),
+            None => Self::key(args, repo, config.ticket_pattern())?,
+        };
+
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+        let client = Client::new(jira_url, config.jira_auth());
+
+        let transition = jira::transition_ticket(&client, &key, status).await?;
+        println!("Moved {key} to {}", transition.to.name);
+
+        Ok(())
+    }
+
+    fn browse<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<()>
+    where
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+    {
+        let key = Self::key(args, repo, config.ticket_pattern())?;
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+
+        let url = jira::browse_url(jira_url, &key);
+
         Command::new("open")
             .arg(url.as_str())
             .stdout(Stdio::null())
@@ -36,4 +254,29 @@ impl Ticket {
 
         Ok(())
     }
+
+    async fn find_prs(
+        key: &str,
+        repo: &Repository,
+        config: &Config,
+    ) -> Result<Vec<PullRequestView>> {
+        match config.provider {
+            Provider::BitBucket => gitbucket::Prs::find_for_ticket(key, repo, config).await,
+            Provider::GitLab => gitlad::Prs::find_for_ticket(key, repo, config).await,
+            Provider::GitHub => gighub::Prs::find_for_ticket(key, repo, config).await,
+        }
+    }
+
+    fn key(args: &ArgMatches, repo: &Repository, pattern: &TicketPattern) -> Result<String> {
+        // plain `ticket` has no key argument
+        if let Some(key) = args.try_get_one::<String>("key").ok().flatten() {
+            return Ok(key.clone());
+        }
+
+        let branch = get_current_branch(repo).ok_or(Error::Detached)?;
+
+        pattern
+            .find_for_branch(&branch, None, Some(repo))
+            .ok_or(Error::NoJiraTicket(branch.to_string()))
+    }
 }
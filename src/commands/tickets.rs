@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::git::{Config, JiraUrlConfig, Provider, TicketPatternConfig, TicketsJqlConfig};
+use crate::jira::{self, Client};
+use crate::view::{self, Format, PullRequestView, TicketRowView};
+use crate::{gighub, gitbucket, gitlad};
+use crate::{Error, Result};
+
+use clap::ArgMatches;
+use git2::{BranchType, Repository};
+
+pub struct Tickets;
+
+impl Tickets {
+    pub async fn handle(args: &ArgMatches, repo: &Repository, config: &Config) -> Result<()> {
+        let jira_url = config.jira_url().ok_or(Error::JiraUrlNotConfigured)?;
+        let jql = args
+            .get_one::<String>("jql")
+            .map(String::as_str)
+            .unwrap_or(config.tickets_jql());
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let client = Client::new(jira_url, config.jira_auth());
+        let issues = jira::pull(&client, jql, "summary,status").await?;
+
+        let mut branches = Self::local_branches(repo, config);
+
+        // local branches are still worth showing when the provider can't be reached
+        let prs = Self::open_prs(args, repo, config)
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!("warning: can't get open PRs: {err}");
+                vec![]
+            });
+
+        let rows = issues
+            .into_iter()
+            .map(|issue| {
+                let pr = prs.iter().find(|pr| {
+                    pr.ticket
+                        .as_ref()
+                        .is_some_and(|ticket| ticket.key.eq_ignore_ascii_case(&issue.key))
+                });
+
+                TicketRowView {
+                    branches: branches
+                        .remove(&issue.key.to_ascii_uppercase())
+                        .unwrap_or_default(),
+                    pr: pr.map(|pr| pr.id),
+                    ci: pr.and_then(|pr| pr.ci),
+                    key: issue.key,
+                    summary: issue.fields.summary,
+                    status: issue.fields.status.name,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() && format == Format::Table {
+            println!("No tickets found");
+            return Ok(());
+        }
+
+        view::print(&rows, format);
+
+        Ok(())
+    }
+
+    /// Local branch names by the ticket key they reference.
+    fn local_branches(repo: &Repository, config: &Config) -> HashMap<String, Vec<String>> {
+        let pattern = config.ticket_pattern();
+        let mut result = HashMap::<_, Vec<_>>::new();
+
+        let branches = match repo.branches(Some(BranchType::Local)) {
+            Ok(branches) => branches,
+            Err(_) => return result,
+        };
+
+        for (branch, _) in branches.flatten() {
+            let name = match branch.name() {
+                Ok(Some(name)) => name,
+                _ => continue,
+            };
+
+            if let Some(key) = pattern.find(name) {
+                result.entry(key).or_default().push(name.to_string());
+            }
+        }
+
+        result
+    }
+
+    async fn open_prs(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Config,
+    ) -> Result<Vec<PullRequestView>> {
+        match config.provider {
+            Provider::BitBucket => gitbucket::Prs::collect(args, repo, config, false).await,
+            Provider::GitLab => gitlad::Prs::collect(args, repo, config).await,
+            Provider::GitHub => gighub::Prs::collect(args, repo, config, false).await,
+        }
+    }
+}
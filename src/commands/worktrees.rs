@@ -0,0 +1,86 @@
+use crate::git::{remove_pr_worktree, PrWorktree};
+use crate::view::{self, Format, PullRequestState, WorktreeRowView};
+use crate::Result;
+
+use clap::ArgMatches;
+use git2::Repository;
+
+/// `pr worktrees` of every provider, `states` are the states of the worktrees' PRs.
+pub struct Worktrees;
+
+impl Worktrees {
+    pub fn handle(
+        args: &ArgMatches,
+        worktrees: Vec<PrWorktree>,
+        states: Vec<Option<PullRequestState>>,
+        repo: &Repository,
+    ) -> Result<()> {
+        match args.subcommand() {
+            Some(("prune", _)) => Self::prune(&worktrees, &states, repo),
+            _ => Self::list(args, worktrees, states),
+        }
+    }
+
+    fn list(
+        args: &ArgMatches,
+        worktrees: Vec<PrWorktree>,
+        states: Vec<Option<PullRequestState>>,
+    ) -> Result<()> {
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        if worktrees.is_empty() && format == Format::Table {
+            println!("No PR worktrees, add one with `pr checkout --worktree`");
+            return Ok(());
+        }
+
+        let rows = worktrees
+            .into_iter()
+            .zip(states)
+            .map(|(worktree, state)| WorktreeRowView {
+                pr: worktree.id,
+                state,
+                branch: worktree.branch,
+                path: worktree.path,
+            })
+            .collect::<Vec<_>>();
+
+        view::print(&rows, format);
+
+        Ok(())
+    }
+
+    fn prune(
+        worktrees: &[PrWorktree],
+        states: &[Option<PullRequestState>],
+        repo: &Repository,
+    ) -> Result<()> {
+        let mut pruned = 0;
+
+        for (worktree, state) in worktrees.iter().zip(states) {
+            // PRs that can't be found are left alone, they might be of another remote
+            if !matches!(
+                state,
+                Some(PullRequestState::Closed | PullRequestState::Merged)
+            ) {
+                continue;
+            }
+
+            match remove_pr_worktree(worktree, repo) {
+                Ok(()) => {
+                    println!("Removed {} of PR #{}", worktree.path.display(), worktree.id);
+                    pruned += 1;
+                }
+                Err(err) => eprintln!("warning: {err}"),
+            }
+        }
+
+        if pruned == 0 {
+            println!("No worktrees of closed PRs");
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
-use crate::git::ConfigError;
+use crate::git::{ConfigError, Provider};
 use crate::shellquote::SplitError;
 
 use chipp_http::Error as HttpError;
 use git2::Error as GitError;
 use std::error::Error as StdError;
 use std::io::Error as IoError;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum Error {
@@ -12,25 +13,67 @@ pub enum Error {
 
     InvalidRepo,
     Detached,
+    NoRemote,
+    AnonymousRemote,
 
     GetConfig(ConfigError),
     InvalidAlias(String, SplitError),
 
     Git(GitError),
-    Http(HttpError),
+    Http(Box<HttpError>),
 
     OpenUrl(IoError, url::Url),
     JiraUrlNotConfigured,
     NoJiraTicket(String),
-
-    NoPrsForBranch(String, HttpError),
-    NoPrWithId(u16, HttpError),
+    UnknownTransition(String, String),
+    NoJiraProject,
+    InvalidIssueKey(String),
+    NotSupportedByTracker(String),
+    IssueNotClosed(String, String),
+
+    WriteHook(PathBuf, IoError),
+    ChainedHookExists(PathBuf),
+    CommitMessage(PathBuf, IoError),
+    NoTicketInCommit(String),
+    EmptyCommitMessage,
+
+    NoPrsForBranch(String, Box<HttpError>),
+    NoPrWithId(u16, Box<HttpError>),
+    NoOpenPrForBranch(String),
+    NotMerged(u16, String),
+
+    PushRejected(String, String),
+    PushFailed(String),
+    StaleLease(String),
+    BranchBehind(String),
+    BranchDiverged(String),
+
+    WorktreeLocked(PathBuf),
+    WorktreeHasChanges(PathBuf),
 
     NotInWorkTree,
 
     RepoExistsAndPublic(String),
     RemoteExists(String, String),
 
+    NotProviderRepo(String, Provider),
+    UnknownHost(String),
+    HostNotSpecified(Provider),
+
+    ReadWorkspace(PathBuf, IoError),
+    WorkspaceGroupNotConfigured(String),
+
+    UnknownColumn(String),
+    InvalidTemplate(String),
+
+    Terminal(IoError),
+    InvalidChoice(String),
+    CopyToClipboard(IoError),
+    NoClipboard,
+
+    ReadCredential(IoError),
+    NoConfiguredHosts,
+
     FailedToExecuteGit(IoError),
 }
 
@@ -42,7 +85,7 @@ impl From<GitError> for Error {
 
 impl From<HttpError> for Error {
     fn from(err: HttpError) -> Error {
-        Error::Http(err)
+        Error::Http(Box::new(err))
     }
 }
 
@@ -64,6 +107,12 @@ impl StdError for Error {
             NoPrWithId(_, err) => Some(err),
             GetConfig(err) => Some(err),
             InvalidAlias(_, err) => Some(err),
+            ReadWorkspace(_, err) => Some(err),
+            Terminal(err) => Some(err),
+            CopyToClipboard(err) => Some(err),
+            ReadCredential(err) => Some(err),
+            WriteHook(_, err) => Some(err),
+            CommitMessage(_, err) => Some(err),
             FailedToExecuteGit(err) => Some(err),
             _ => None,
         }
@@ -80,6 +129,8 @@ impl fmt::Display for Error {
 
             InvalidRepo => write!(f, "this is not a bitbucket repository"),
             Detached => write!(f, "can't find the current branch"),
+            NoRemote => write!(f, "the repository has no remotes"),
+            AnonymousRemote => write!(f, "the remote has no name"),
 
             GetConfig(err) => write!(f, "{}", err),
             InvalidAlias(alias, _) => write!(f, "invalid alias for `{alias}`"),
@@ -92,20 +143,106 @@ impl fmt::Display for Error {
             NoJiraTicket(branch) => {
                 write!(f, "can't find JIRA ticket in branch name `{}`", branch)
             }
+            UnknownTransition(ticket, status) => write!(
+                f,
+                "{ticket} can't be moved to `{status}`, see `git ticket transitions {ticket}`"
+            ),
+            NoJiraProject => write!(
+                f,
+                "can't tell the JIRA project, pass --project or set gitext.ticketProjects"
+            ),
+            InvalidIssueKey(key) => write!(f, "`{key}` is not an issue number"),
+            NotSupportedByTracker(command) => {
+                write!(f, "`{command}` is only supported for JIRA tickets")
+            }
+            IssueNotClosed(key, message) => write!(f, "issue {key} wasn't closed: {message}"),
+
+            WriteHook(path, err) => write!(f, "can't write hook {}: {}", path.display(), err),
+            ChainedHookExists(path) => write!(
+                f,
+                "can't chain the existing hook, {} already exists",
+                path.display()
+            ),
+            CommitMessage(path, err) => {
+                write!(f, "can't update commit message {}: {}", path.display(), err)
+            }
+            NoTicketInCommit(key) => write!(
+                f,
+                "the commit message doesn't mention {key}, add it or commit with --no-verify"
+            ),
+            EmptyCommitMessage => write!(f, "aborting commit due to empty commit message"),
 
             NoPrsForBranch(branch, err) => {
                 write!(f, "can't find prs for branch {}: {}", branch, err)
             }
             NoPrWithId(id, err) => write!(f, "can't find pr with id {}: {}", id, err),
+            NoOpenPrForBranch(branch) => write!(f, "there is no open pr for branch {branch}"),
+            NotMerged(id, message) => write!(f, "pr {id} wasn't merged: {message}"),
+
+            PushRejected(branch, reason) => write!(f, "pushing {branch} was rejected: {reason}"),
+            PushFailed(branch) => write!(f, "can't push {branch}"),
+            StaleLease(branch) => write!(
+                f,
+                "{branch} changed on the remote since it was last fetched, fetch and check it before forcing"
+            ),
+            BranchBehind(branch) => {
+                write!(f, "{branch} is behind its remote branch, pull before pushing")
+            }
+            BranchDiverged(branch) => write!(
+                f,
+                "{branch} and its remote branch have diverged, pass --force to overwrite the remote one"
+            ),
+
+            WorktreeLocked(path) => write!(f, "worktree {} is locked", path.display()),
+            WorktreeHasChanges(path) => {
+                write!(f, "worktree {} has uncommitted changes", path.display())
+            }
 
             RepoExistsAndPublic(repo) => {
                 write!(f, "repo `{repo}` already exists and is public")
             }
 
+            NotProviderRepo(repo, provider) => {
+                write!(f, "`{repo}` doesn't name a {provider} repository")
+            }
+            UnknownHost(host) => write!(
+                f,
+                "the provider of {host} is unknown, pass --provider or set gitext.host.{host}.provider"
+            ),
+            HostNotSpecified(provider) => {
+                write!(f, "pass --host with the host of the {provider} server")
+            }
+
             RemoteExists(remote, url) => {
                 write!(f, "remote `{remote}` already exists with url `{url}`")
             }
 
+            ReadWorkspace(path, err) => {
+                write!(f, "can't read workspace {}: {}", path.display(), err)
+            }
+            WorkspaceGroupNotConfigured(group) => {
+                write!(f, "workspace group `{group}` is not configured")
+            }
+
+            UnknownColumn(column) => write!(f, "unknown column `{column}`"),
+            InvalidTemplate(template) => {
+                write!(f, "invalid template `{template}`: unclosed placeholder")
+            }
+
+            Terminal(err) => write!(f, "terminal error: {}", err),
+            InvalidChoice(answer) => write!(f, "invalid choice `{answer}`"),
+            CopyToClipboard(err) => write!(f, "can't copy to clipboard: {}", err),
+            NoClipboard => write!(
+                f,
+                "can't copy to clipboard, none of pbcopy, wl-copy, xclip or xsel is installed"
+            ),
+
+            ReadCredential(err) => write!(f, "can't read the credential from git: {}", err),
+            NoConfiguredHosts => write!(
+                f,
+                "no hosts are configured, run it in a repository or set gitext.host.<host>.provider"
+            ),
+
             NotInWorkTree => write!(f, "not in a git repository"),
 
             FailedToExecuteGit(err) => write!(f, "failed to execute git: {}", err),
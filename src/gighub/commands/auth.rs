@@ -1,27 +1,28 @@
 use crate::error::Error;
 use crate::git::{AuthDomainConfig, BaseUrlConfig};
 use crate::github::Client;
+use crate::view::{self, Format, UserView};
 
 pub struct Auth;
 
 impl Auth {
-    pub async fn handle<Conf>(config: &Conf) -> Result<(), Error>
+    pub async fn handle<Conf>(config: &Conf, format: Format) -> Result<(), Error>
     where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
     {
-        match Self::print_whoami(config).await {
+        match Self::print_whoami(config, format).await {
             Ok(_) => Ok(()),
             Err(_) => {
                 chipp_auth::reset_user_and_pass(config.auth_domain());
-                Self::print_whoami(config)
+                Self::print_whoami(config, format)
                     .await
                     .map_err(|_| Error::AuthorizationError)
             }
         }
     }
 
-    async fn print_whoami<Conf>(config: &Conf) -> Result<(), chipp_http::Error>
+    async fn print_whoami<Conf>(config: &Conf, format: Format) -> Result<(), chipp_http::Error>
     where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
@@ -30,7 +31,17 @@ impl Auth {
 
         match client.whoami().await {
             Ok(user) => {
-                println!("You're logged in as {}", user.login);
+                match format {
+                    Format::Table => println!("You're logged in as {}", user.login),
+                    format => {
+                        let user = UserView {
+                            login: user.login,
+                            name: user.name,
+                        };
+                        view::print_one(&user, format);
+                    }
+                }
+
                 Ok(())
             }
             Err(err) => Err(err),
@@ -2,6 +2,7 @@ use std::path::Path;
 
 use clap::ArgMatches;
 
+use crate::cli::CloneTarget;
 use crate::error::Error;
 use crate::git::{clone_repo, set_config, Config};
 use crate::github::{Client, RepoId};
@@ -10,10 +11,14 @@ pub struct Clone;
 
 impl Clone {
     pub async fn handle(args: &ArgMatches, config: &Config, path: &Path) -> Result<(), Error> {
-        let repo_id: &RepoId = args.get_one("repo").unwrap();
+        let target: &CloneTarget = args.get_one("repo").unwrap();
+        let repo_id = target
+            .segments(&config.base_url)
+            .and_then(|segments| RepoId::from_path(&segments))
+            .ok_or_else(|| Error::NotProviderRepo(target.to_string(), config.provider))?;
 
         let client = Client::new(config);
-        let repository = client.get_repo(repo_id).await?;
+        let repository = client.get_repo(&repo_id).await?;
 
         let mut path = path.to_path_buf();
         path.push(repo_id.repo.clone());
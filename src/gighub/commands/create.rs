@@ -1,4 +1,4 @@
-use crate::git::{AuthDomainConfig, BaseUrlConfig};
+use crate::git::{get_current_branch, push_branch, AuthDomainConfig, BaseUrlConfig};
 use crate::github::{Client, RepoId};
 use crate::Error;
 
@@ -70,6 +70,12 @@ impl Create {
             println!("updated remote `{remote_name}`");
         }
 
+        if args.get_flag("push") {
+            let branch = get_current_branch(repo).ok_or(Error::Detached)?;
+            let mut remote = repo.find_remote(remote_name)?;
+            push_branch(&mut remote, &branch, true, false, repo, config)?;
+        }
+
         println!("{}", repository.html_url);
 
         Ok(())
@@ -1,11 +1,23 @@
-use crate::git::{AuthDomainConfig, BaseUrlConfig};
-use crate::github::{get_current_repo_id, Client, Conclusion, PullRequest, RepoId, State, Status};
+use std::collections::HashMap;
+
+use crate::git::{
+    AuthDomainConfig, BaseUrlConfig, JiraUrlConfig, PrsColumnsConfig, TicketPattern,
+    TicketPatternConfig, Tracker, TrackerConfig,
+};
+use crate::github::{
+    get_current_repo_id, latest_review_states, BranchRule, Client, Conclusion, GitHubTracker,
+    MergeableState, PullRequest, RepoId, ReviewState, State, Status,
+};
+use crate::tracker::IssueTracker;
+use crate::view::{
+    self, ApprovalsView, CiStatus, Column, Mergeability, Output, PullRequestView, TicketView,
+    UserView,
+};
 use crate::ArgMatches;
 use crate::Error;
 
 use futures::{stream, StreamExt};
 use git2::Repository;
-use prettytable::{cell, row, Table};
 
 pub struct Prs;
 
@@ -22,103 +34,310 @@ impl Prs {
     where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: PrsColumnsConfig,
+        Conf: TrackerConfig,
     {
-        let repo_id = get_current_repo_id(&repo, config).ok_or(Error::InvalidRepo)?;
-        let client = Client::new(config);
+        // GitHub lists PRs without their mergeable state, it takes a request for each PR
+        let mut defaults = Column::defaults(config);
+        defaults.retain(|column| *column != Column::Merge);
 
-        let filter_mode = Self::filter_mode(args, &client).await;
+        let output = Output::from_args(args, config.prs_columns(), defaults)?;
+        let views = Self::collect(args, repo, config, output.selects(Column::Merge)).await?;
 
-        let mut prs = client.find_open_prs(&repo_id).await?;
+        output.print(&views, "No open PRs for that repo");
 
-        match filter_mode {
-            Some(FilterMode::ByAuthor(id)) => {
-                prs = prs.into_iter().filter(|pr| pr.user.id == id).collect()
-            }
-            None => {}
-        }
+        Ok(())
+    }
 
-        if prs.is_empty() {
-            println!("No open PRs for that repo");
-            return Ok(());
-        }
+    /// Open PRs of the repository, `mergeability` fetches the mergeable state of each one.
+    pub async fn collect<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+        mergeability: bool,
+    ) -> Result<Vec<PullRequestView>, Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+    {
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
 
-        Self::print_table_for_prs(&prs, false, &repo_id, config).await;
+        let prs = Self::find_filtered_prs(args, &client, &repo_id).await?;
 
-        Ok(())
+        Ok(Self::views_for_prs(&prs, &repo_id, config, mergeability).await)
     }
 
-    pub async fn print_table_for_prs<Conf>(
+    /// Recently updated PRs whose branch or title references the ticket `key`.
+    pub async fn find_for_ticket<Conf>(
+        key: &str,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<Vec<PullRequestView>, Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+    {
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
+
+        let pattern = config.ticket_pattern();
+        let prs = client
+            .find_recent_prs(&repo_id)
+            .await?
+            .into_iter()
+            .filter(|pr| {
+                pattern.references(&pr.head.reference, key) || pattern.references(&pr.title, key)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self::views_for_prs(&prs, &repo_id, config, false).await)
+    }
+
+    pub async fn views_for_prs<Conf>(
         prs: &[PullRequest],
-        show_status: bool,
         repo_id: &RepoId,
         config: &Conf,
-    ) where
+        mergeability: bool,
+    ) -> Vec<PullRequestView>
+    where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
     {
-        let mut table = Table::new();
-
-        {
-            let mut row = row![];
-            row.add_cell(cell!("ID"));
-            row.add_cell(cell!("Author"));
-            row.add_cell(cell!("Title"));
-            row.add_cell(cell!("CI"));
-            row.add_cell(cell!("Target"));
-            row.add_cell(cell!("Last updated"));
-
-            if show_status {
-                row.add_cell(cell!("Status"));
+        let client = Client::new(config);
+        let statuses = Self::get_checks_statuses_for_prs(prs, repo_id, &client).await;
+        let approvals = Self::get_approvals_for_prs(prs, repo_id, &client).await;
+        let mergeable_states = if mergeability {
+            Self::get_mergeable_states_for_prs(prs, repo_id, &client).await
+        } else {
+            prs.iter().map(|pr| pr.mergeable_state).collect()
+        };
+
+        let keys = prs
+            .iter()
+            .filter_map(|pr| {
+                config
+                    .ticket_pattern()
+                    .find_in(&[&pr.head.reference, &pr.title])
+            })
+            .collect::<Vec<_>>();
+        let tickets = keys.iter().map(String::as_str).collect::<Vec<_>>();
+        let tickets = match config.tracker() {
+            Tracker::Jira => crate::jira::statuses_for_tickets(&tickets, config)
+                .await
+                .unwrap_or_default(),
+            Tracker::Issues => {
+                let tracker = GitHubTracker {
+                    client: Client::new(config),
+                    repo_id,
+                    base_url: config.base_url(),
+                };
+
+                tracker.states(&tickets).await
             }
+        };
+
+        prs.iter()
+            .zip(statuses)
+            .zip(approvals)
+            .zip(mergeable_states)
+            .map(|(((pr, status), approvals), mergeable_state)| {
+                let mergeability = Self::mergeability(mergeable_state, status);
+                Self::view_for_pr(
+                    pr,
+                    repo_id,
+                    status,
+                    approvals,
+                    mergeability,
+                    &tickets,
+                    config.ticket_pattern(),
+                )
+            })
+            .collect()
+    }
+
+    async fn find_filtered_prs(
+        args: &ArgMatches,
+        client: &Client<'_>,
+        repo_id: &RepoId,
+    ) -> Result<Vec<PullRequest>, Error> {
+        let filter_mode = Self::filter_mode(args, client).await;
 
-            table.set_titles(row);
+        let mut prs = client.find_open_prs(repo_id).await?;
+
+        match filter_mode {
+            Some(FilterMode::ByAuthor(id)) => prs.retain(|pr| pr.user.id == id),
+            None => {}
         }
 
-        let client = Client::new(config);
+        Ok(prs)
+    }
 
-        let statuses = stream::iter(
+    async fn get_checks_statuses_for_prs(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> Vec<Option<CiStatus>> {
+        stream::iter(
             prs.iter()
-                .map(|pr| Self::pr_checks_status(pr, repo_id, &client)),
+                .map(|pr| Self::pr_checks_status(pr, repo_id, client)),
         )
         .buffered(10)
         .collect::<Vec<_>>()
-        .await;
-
-        for (pr, status) in prs.into_iter().zip(statuses.into_iter()) {
-            let mut row = row![pr.number, pr.user.login, title_for_pr(&pr, 35)];
+        .await
+    }
 
-            let updated = pr.updated_at - chrono::Utc::now();
-            let updated = chrono_humanize::HumanTime::from(updated);
+    async fn get_mergeable_states_for_prs(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> Vec<Option<MergeableState>> {
+        stream::iter(prs.iter().map(|pr| async move {
+            match pr.mergeable_state {
+                Some(state) => Some(state),
+                None => {
+                    client
+                        .get_pr_by_id(pr.number, repo_id)
+                        .await
+                        .ok()?
+                        .mergeable_state
+                }
+            }
+        }))
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await
+    }
 
-            match status {
-                Some(ChecksStatus::Passed) => row.add_cell(cell!(Fg->"A")),
-                Some(ChecksStatus::InProgress) => row.add_cell(cell!(Fy->"I")),
-                Some(ChecksStatus::Failed) => row.add_cell(cell!(Fr->"X")),
-                None => row.add_cell(cell!(" ")),
+    fn mergeability(state: Option<MergeableState>, ci: Option<CiStatus>) -> Option<Mergeability> {
+        match state? {
+            MergeableState::Clean | MergeableState::Unstable | MergeableState::HasHooks => {
+                Some(Mergeability::Mergeable)
             }
+            MergeableState::Dirty => Some(Mergeability::Conflicts),
+            MergeableState::Behind => Some(Mergeability::Behind),
+            // GitHub doesn't tell whether reviews or required checks block the PR
+            MergeableState::Blocked => match ci {
+                Some(CiStatus::Passed) | None => Some(Mergeability::Blocked),
+                Some(_) => Some(Mergeability::BlockedByChecks),
+            },
+            MergeableState::Draft => Some(Mergeability::Draft),
+            MergeableState::Unknown => None,
+        }
+    }
+
+    async fn get_approvals_for_prs(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> Vec<Option<ApprovalsView>> {
+        let mut targets = prs
+            .iter()
+            .map(|pr| pr.base.reference.as_str())
+            .collect::<Vec<_>>();
+        targets.sort_unstable();
+        targets.dedup();
+
+        let required = stream::iter(
+            targets
+                .iter()
+                .map(|target| Self::required_approvals(target, repo_id, client)),
+        )
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await;
 
-            row.add_cell(cell!(pr.base.label));
-            row.add_cell(cell!(updated));
+        let required = targets.into_iter().zip(required).collect::<HashMap<_, _>>();
 
-            if show_status {
-                match status_for_pr(&pr) {
-                    PullRequestStatus::Open => row.add_cell(cell!(Fy->"Open")),
-                    PullRequestStatus::Merged => row.add_cell(cell!(Fg->"Merged")),
-                    PullRequestStatus::Closed => row.add_cell(cell!(Fr->"Closed")),
-                }
-            }
+        stream::iter(prs.iter().map(|pr| {
+            let required = required.get(pr.base.reference.as_str()).copied().flatten();
+            Self::pr_approvals(pr, required, repo_id, client)
+        }))
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await
+    }
+
+    async fn pr_approvals(
+        pr: &PullRequest,
+        required: Option<u16>,
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> Option<ApprovalsView> {
+        let reviews = client.get_pr_reviews(pr.number, repo_id).await.ok()?;
+        let states = latest_review_states(&reviews);
+
+        let mut reviewers = pr
+            .requested_reviewers
+            .iter()
+            .map(|user| user.id)
+            .chain(states.keys().copied())
+            .filter(|id| *id != pr.user.id)
+            .collect::<Vec<_>>();
+        reviewers.sort_unstable();
+        reviewers.dedup();
+
+        let approved = states
+            .values()
+            .filter(|state| **state == ReviewState::Approved)
+            .count();
+
+        Some(ApprovalsView {
+            approved: approved as u16,
+            reviewers: Some(reviewers.len() as u16),
+            required,
+        })
+    }
 
-            table.add_row(row);
+    async fn required_approvals(
+        target: &str,
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> Option<u16> {
+        let from_rules = client
+            .get_branch_rules(target, repo_id)
+            .await
+            .ok()
+            .and_then(|rules| {
+                rules
+                    .into_iter()
+                    .filter_map(|rule| match rule {
+                        BranchRule::PullRequest { parameters } => {
+                            Some(parameters.required_approving_review_count)
+                        }
+                        BranchRule::Other => None,
+                    })
+                    .max()
+            });
+
+        if from_rules.is_some() {
+            return from_rules;
         }
 
-        table.printstd();
+        // classic branch protection is only visible to admins of the repo
+        client
+            .get_required_reviews(target, repo_id)
+            .await
+            .ok()
+            .map(|reviews| reviews.required_approving_review_count)
     }
 
     async fn pr_checks_status(
         pr: &PullRequest,
         repo_id: &RepoId,
         client: &Client<'_>,
-    ) -> Option<ChecksStatus> {
+    ) -> Option<CiStatus> {
         let response = client
             .get_commit_check_suites(repo_id, pr.head.sha.as_str())
             .await
@@ -130,14 +349,61 @@ impl Prs {
         let check = checks.last()?;
 
         match (&check.status, check.conclusion.as_ref()) {
-            (Status::Queued, _) | (Status::InProgress, _) => Some(ChecksStatus::InProgress),
-            (Status::Completed, Some(Conclusion::Success)) => Some(ChecksStatus::Passed),
-            (Status::Completed, _) => Some(ChecksStatus::Failed),
+            (Status::Queued, _) => Some(CiStatus::Pending),
+            (Status::InProgress, _) => Some(CiStatus::Running),
+            (Status::Completed, Some(Conclusion::Success)) => Some(CiStatus::Passed),
+            (Status::Completed, _) => Some(CiStatus::Failed),
+        }
+    }
+
+    pub fn view_state(pr: &PullRequest) -> view::PullRequestState {
+        match pr.state {
+            State::Open => view::PullRequestState::Open,
+            State::Closed if pr.merged_at.is_some() => view::PullRequestState::Merged,
+            State::Closed => view::PullRequestState::Closed,
+        }
+    }
+
+    fn view_for_pr(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        ci: Option<CiStatus>,
+        approvals: Option<ApprovalsView>,
+        mergeability: Option<Mergeability>,
+        tickets: &HashMap<String, String>,
+        pattern: &TicketPattern,
+    ) -> PullRequestView {
+        let state = Self::view_state(pr);
+
+        PullRequestView {
+            repository: format!("{}/{}", repo_id.owner, repo_id.repo),
+            id: pr.number,
+            title: pr.title.clone(),
+            url: pr.url.clone(),
+            author: UserView {
+                login: pr.user.login.clone(),
+                name: pr.user.name.clone(),
+            },
+            source: pr.head.reference.clone(),
+            target: pr.base.reference.clone(),
+            created: pr.created_at,
+            updated: pr.updated_at,
+            state,
+            ci,
+            approvals,
+            mergeability,
+            ticket: pattern
+                .find_in(&[&pr.head.reference, &pr.title])
+                .map(|key| TicketView::new(&key, tickets)),
+            labels: pr.labels.iter().map(|label| label.name.clone()).collect(),
         }
     }
 
     async fn filter_mode(args: &ArgMatches, client: &Client<'_>) -> Option<FilterMode> {
-        match args.get_one::<String>("filter").map(String::as_str) {
+        // `switch` picks from all open PRs and has no filter argument
+        let filter = args.try_get_one::<String>("filter").ok().flatten();
+
+        match filter.map(String::as_str) {
             Some("my") => {
                 let user = client.whoami().await.ok()?;
                 Some(FilterMode::ByAuthor(user.id))
@@ -154,33 +420,3 @@ impl Prs {
         }
     }
 }
-
-enum ChecksStatus {
-    Passed,
-    Failed,
-    InProgress,
-}
-
-enum PullRequestStatus {
-    Open,
-    Merged,
-    Closed,
-}
-
-fn title_for_pr(pr: &PullRequest, max_width: usize) -> String {
-    use hyphenation::{Language, Load, Standard};
-    use textwrap::{fill, Options, WordSplitter::Hyphenation};
-
-    let hyphenator = Standard::from_embedded(Language::EnglishUS).unwrap();
-    let options = Options::new(max_width).word_splitter(Hyphenation(hyphenator));
-
-    fill(&pr.title, options)
-}
-
-fn status_for_pr(pr: &PullRequest) -> PullRequestStatus {
-    match pr.state {
-        State::Open => PullRequestStatus::Open,
-        State::Closed if pr.merged_at.is_some() => PullRequestStatus::Merged,
-        State::Closed => PullRequestStatus::Closed,
-    }
-}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+
+use crate::git::{AuthDomainConfig, BaseUrlConfig};
+use crate::github::{get_current_repo_id, Client, Conclusion, PullRequest, RepoId, ReviewState};
+use crate::view::{self, Format, PullRequestHistory, PullRequestStats};
+use crate::Error;
+
+use clap::ArgMatches;
+use futures::{stream, StreamExt};
+use git2::Repository;
+
+pub struct Stats;
+
+impl Stats {
+    pub async fn handle<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        match args.subcommand() {
+            Some(("prs", args)) => Self::prs(args, repo, config).await,
+            _ => unreachable!("subcommand is required"),
+        }
+    }
+
+    async fn prs<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        let since = *args.get_one::<DateTime<Utc>>("since").expect("default");
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
+
+        let prs = Self::find_closed_prs(&client, &repo_id, since).await?;
+
+        let history = stream::iter(
+            prs.iter()
+                .map(|pr| Self::history_for_pr(pr, &repo_id, &client)),
+        )
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await;
+
+        let repository = format!("{}/{}", repo_id.owner, repo_id.repo);
+        let stats = PullRequestStats::new(repository, since, &history);
+
+        view::print_stats(&stats, format);
+
+        Ok(())
+    }
+
+    async fn find_closed_prs(
+        client: &Client<'_>,
+        repo_id: &RepoId,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<PullRequest>, Error> {
+        let mut result = vec![];
+        let mut page = 1;
+
+        loop {
+            let prs = client.find_closed_prs(repo_id, page).await?;
+
+            // PRs are sorted by update time, nothing closed after `since` can follow
+            let is_last_page = prs.len() < 100 || prs.last().is_none_or(|pr| pr.updated_at < since);
+
+            result.extend(
+                prs.into_iter()
+                    .filter(|pr| pr.closed_at.is_some_and(|closed| closed >= since)),
+            );
+
+            if is_last_page {
+                return Ok(result);
+            }
+
+            page += 1;
+        }
+    }
+
+    async fn history_for_pr(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> PullRequestHistory {
+        let reviews = client
+            .get_pr_reviews(pr.number, repo_id)
+            .await
+            .unwrap_or_default();
+
+        let reviews = reviews
+            .iter()
+            .filter(|review| review.state != ReviewState::Pending)
+            .filter_map(|review| Some((review.user.as_ref()?, review.submitted_at)))
+            .filter(|(user, _)| user.id != pr.user.id)
+            .collect::<Vec<_>>();
+
+        let mut reviewers = reviews
+            .iter()
+            .map(|(user, _)| user.login.clone())
+            .collect::<Vec<_>>();
+        reviewers.sort_unstable();
+        reviewers.dedup();
+
+        // the list endpoint doesn't include the diff stats
+        let size = client
+            .get_pr_by_id(pr.number, repo_id)
+            .await
+            .ok()
+            .and_then(|pr| pr.changed_files);
+
+        let (ci_runs, ci_failures) =
+            match client.get_commit_check_suites(repo_id, &pr.head.sha).await {
+                Ok(suites) => {
+                    let conclusions = suites
+                        .check_suites
+                        .iter()
+                        .filter_map(|suite| suite.conclusion.as_ref())
+                        .filter(|conclusion| {
+                            !matches!(conclusion, Conclusion::Neutral | Conclusion::Skipped)
+                        })
+                        .collect::<Vec<_>>();
+
+                    let failures = conclusions
+                        .iter()
+                        .filter(|conclusion| {
+                            matches!(conclusion, Conclusion::Failure | Conclusion::TimedOut)
+                        })
+                        .count();
+
+                    (conclusions.len() as u32, failures as u32)
+                }
+                Err(_) => (0, 0),
+            };
+
+        PullRequestHistory {
+            created: pr.created_at,
+            merged: pr.merged_at,
+            first_review: reviews.iter().filter_map(|(_, submitted)| *submitted).min(),
+            size,
+            reviewers,
+            ci_runs,
+            ci_failures,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use crate::git::{fetch_remote, switch_to_branch, AuthDomainConfig, BaseUrlConfig};
+use crate::git::{
+    fetch_remote, switch_with_autostash, AuthDomainConfig, AutostashConfig, BaseUrlConfig,
+    FetchSpec, JiraUrlConfig, TicketPatternConfig, TrackerConfig,
+};
 use crate::github::{get_current_repo_id, get_github_remote, Client, PullRequest};
 use crate::Error;
 
@@ -18,10 +21,21 @@ impl Switch {
     where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+        Conf: AutostashConfig,
     {
         let repo_id = get_current_repo_id(&repo, config).ok_or(Error::InvalidRepo)?;
 
-        let id: &str = args.get_one::<String>("id").expect("required");
+        let id: &str = match args.get_one::<String>("id") {
+            Some(id) => id,
+            None => {
+                super::pr::Pr::pick(args, repo, config).await?;
+                return Ok(true);
+            }
+        };
+
         let id = match u16::from_str(id.as_ref()) {
             Ok(id) => id,
             Err(_) => return Ok(false),
@@ -31,24 +45,32 @@ impl Switch {
         let pr = client
             .get_pr_by_id(id, &repo_id)
             .await
-            .map_err(|err| Error::NoPrWithId(id, err))?;
+            .map_err(|err| Error::NoPrWithId(id, err.into()))?;
 
-        Self::switch(&pr, &repo, config)?;
+        Self::switch(&pr, args, repo, config)?;
 
         Ok(true)
     }
 
-    fn switch<Conf>(pr: &PullRequest, repo: &Repository, config: &Conf) -> Result<(), Error>
+    fn switch<Conf>(
+        pr: &PullRequest,
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
     where
         Conf: BaseUrlConfig,
         Conf: AuthDomainConfig,
+        Conf: AutostashConfig,
     {
         let branch_name = &pr.head.reference;
         let commit_sha = &pr.head.sha;
 
         let mut remote = get_github_remote(&repo, config).unwrap();
-        fetch_remote(&mut remote, repo, config)?;
+        let fetch = FetchSpec::branch(branch_name, args.get_flag("prune"));
+        fetch_remote(&mut remote, fetch, repo, config)?;
 
-        switch_to_branch(branch_name, commit_sha, &remote, repo)
+        let autostash = args.get_flag("autostash") || config.autostash();
+        switch_with_autostash(branch_name, commit_sha, &remote, repo, autostash)
     }
 }
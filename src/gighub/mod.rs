@@ -5,6 +5,7 @@ mod commands {
     pub mod create;
     pub mod pr;
     pub mod prs;
+    pub mod stats;
     pub mod switch;
 }
 
@@ -14,4 +15,5 @@ pub use commands::clone::Clone;
 pub use commands::create::Create;
 pub use commands::pr::Pr;
 pub use commands::prs::Prs;
+pub use commands::stats::Stats;
 pub use commands::switch::Switch;
@@ -0,0 +1,55 @@
+use git2::{Remote, Repository};
+
+use super::{
+    apply_stash, get_current_branch, has_uncommitted_changes, stash_changes, switch_to_branch,
+};
+use crate::view;
+use crate::Error;
+
+/// Stash messages end with it and the branch the changes were made on.
+const AUTOSTASH_MESSAGE: &str = "gitext autostash from";
+
+/// Switches like `switch_to_branch`, stashing uncommitted changes first when `autostash`
+/// is set, and offers to re-apply the changes stashed when leaving `branch_name` earlier.
+pub fn switch_with_autostash(
+    branch_name: &str,
+    commit: &str,
+    remote: &Remote,
+    repo: &Repository,
+    autostash: bool,
+) -> Result<(), Error> {
+    let source = get_current_branch(repo).unwrap_or("HEAD".to_string());
+
+    if autostash && source != branch_name && has_uncommitted_changes(repo)? {
+        stash_changes(&autostash_message(&source), repo)?;
+        println!("stashed uncommitted changes of {source}");
+    }
+
+    switch_to_branch(branch_name, commit, remote, repo)?;
+
+    if let Some(index) = find_autostash(branch_name, repo) {
+        let question = format!("Re-apply the changes stashed when leaving {branch_name}?");
+
+        if view::confirm(&question)? {
+            apply_stash(index, repo)?;
+        } else {
+            println!("the changes are kept in stash@{{{index}}}");
+        }
+    }
+
+    Ok(())
+}
+
+fn autostash_message(branch: &str) -> String {
+    format!("{AUTOSTASH_MESSAGE} {branch}")
+}
+
+/// Index of the newest stash made when leaving `branch`, as in `stash@{index}`.
+fn find_autostash(branch: &str, repo: &Repository) -> Option<usize> {
+    let reflog = repo.reflog("refs/stash").ok()?;
+    let message = autostash_message(branch);
+
+    reflog
+        .iter()
+        .position(|entry| entry.message().is_some_and(|text| text.ends_with(&message)))
+}
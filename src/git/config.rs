@@ -1,28 +1,81 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::error::Error as StdError;
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use git2::{Config as GitConfig, Repository};
 use url::Url;
 
+use super::TicketPattern;
+
+const DEFAULT_ON_START: &str = "In Progress";
+const DEFAULT_ON_CLOSE: &str = "Done";
+const DEFAULT_BRANCH_TEMPLATE: &str = "feature/{key}-{slug}";
+const DEFAULT_TICKETS_JQL: &str = "assignee = currentUser() AND resolution = Unresolved";
+const DEFAULT_WORKTREE_PATH: &str = "../{repo}-pr-{id}";
+const DEFAULT_COMMIT_PREFIX: &str = "{key}: ";
+// `#` at the start of a line is a comment for git
+const DEFAULT_ISSUE_COMMIT_PREFIX: &str = "[#{key}] ";
+
 #[derive(Debug)]
 pub struct Config {
     pub provider: Provider,
+    pub tracker: Tracker,
 
     pub base_url: Url,
     pub auth_domain: String,
 
     pub jira_url: Option<Url>,
+    pub jira_auth: JiraAuth,
+    pub jira_on_pr_create: Option<String>,
+    pub jira_on_merge: Option<String>,
+    pub jira_on_start: Option<String>,
+    pub jira_on_close: String,
+    pub jira_pr_link: Option<JiraPrLink>,
+
+    pub ticket_pattern: TicketPattern,
+    pub ticket_branch_template: String,
+    pub ticket_base_branch: Option<String>,
+    pub ticket_assign_on_start: bool,
+    pub tickets_jql: String,
+
+    pub commit_prefix: String,
+    pub commit_require_key: bool,
+
+    pub autostash: bool,
+    pub worktree_path: String,
+
+    pub prs_columns: Option<String>,
+    pub prs_required_approvals: Option<u16>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             provider: Provider::GitHub,
+            tracker: Tracker::Jira,
             base_url: Url::parse("https://github.com").unwrap(),
             auth_domain: "github.com".to_string(),
             jira_url: None,
+            jira_auth: JiraAuth::Token,
+            jira_on_pr_create: None,
+            jira_on_merge: None,
+            jira_on_start: Some(DEFAULT_ON_START.to_string()),
+            jira_on_close: DEFAULT_ON_CLOSE.to_string(),
+            jira_pr_link: None,
+            ticket_pattern: TicketPattern::default(),
+            ticket_branch_template: DEFAULT_BRANCH_TEMPLATE.to_string(),
+            ticket_base_branch: None,
+            ticket_assign_on_start: false,
+            tickets_jql: DEFAULT_TICKETS_JQL.to_string(),
+            commit_prefix: DEFAULT_COMMIT_PREFIX.to_string(),
+            commit_require_key: false,
+            autostash: false,
+            worktree_path: DEFAULT_WORKTREE_PATH.to_string(),
+            prs_columns: None,
+            prs_required_approvals: None,
         }
     }
 }
@@ -49,15 +102,195 @@ impl AuthDomainConfig for Config {
 
 pub trait JiraUrlConfig {
     fn jira_url(&self) -> Option<&Url>;
+    fn jira_auth(&self) -> JiraAuth;
 }
 
 impl JiraUrlConfig for Config {
     fn jira_url(&self) -> Option<&Url> {
         self.jira_url.as_ref()
     }
+
+    fn jira_auth(&self) -> JiraAuth {
+        self.jira_auth
+    }
+}
+
+/// Statuses to move the branch's ticket to when its PR is created or merged.
+pub trait JiraTransitionsConfig {
+    fn jira_on_pr_create(&self) -> Option<&str>;
+    fn jira_on_merge(&self) -> Option<&str>;
+    fn jira_on_start(&self) -> Option<&str>;
+    fn jira_on_close(&self) -> &str;
+}
+
+impl JiraTransitionsConfig for Config {
+    fn jira_on_pr_create(&self) -> Option<&str> {
+        self.jira_on_pr_create.as_deref()
+    }
+
+    fn jira_on_merge(&self) -> Option<&str> {
+        self.jira_on_merge.as_deref()
+    }
+
+    fn jira_on_start(&self) -> Option<&str> {
+        self.jira_on_start.as_deref()
+    }
+
+    fn jira_on_close(&self) -> &str {
+        &self.jira_on_close
+    }
+}
+
+pub trait TrackerConfig {
+    fn tracker(&self) -> Tracker;
+}
+
+impl TrackerConfig for Config {
+    fn tracker(&self) -> Tracker {
+        self.tracker
+    }
+}
+
+/// How a PR is mentioned on its ticket when it's created or merged.
+pub trait JiraLinkConfig {
+    fn jira_pr_link(&self) -> Option<JiraPrLink>;
+}
+
+impl JiraLinkConfig for Config {
+    fn jira_pr_link(&self) -> Option<JiraPrLink> {
+        self.jira_pr_link
+    }
+}
+
+/// How ticket keys are found in branch names, PR titles and commit subjects.
+pub trait TicketPatternConfig {
+    fn ticket_pattern(&self) -> &TicketPattern;
+}
+
+impl TicketPatternConfig for Config {
+    fn ticket_pattern(&self) -> &TicketPattern {
+        &self.ticket_pattern
+    }
+}
+
+/// How `ticket start` creates a branch for a ticket.
+pub trait TicketStartConfig {
+    fn ticket_branch_template(&self) -> &str;
+    fn ticket_base_branch(&self) -> Option<&str>;
+    fn ticket_assign_on_start(&self) -> bool;
+}
+
+impl TicketStartConfig for Config {
+    fn ticket_branch_template(&self) -> &str {
+        &self.ticket_branch_template
+    }
+
+    fn ticket_base_branch(&self) -> Option<&str> {
+        self.ticket_base_branch.as_deref()
+    }
+
+    fn ticket_assign_on_start(&self) -> bool {
+        self.ticket_assign_on_start
+    }
+}
+
+pub trait TicketsJqlConfig {
+    fn tickets_jql(&self) -> &str;
+}
+
+impl TicketsJqlConfig for Config {
+    fn tickets_jql(&self) -> &str {
+        &self.tickets_jql
+    }
+}
+
+/// What the commit hooks of `hooks install` do with the ticket key.
+pub trait CommitHooksConfig {
+    /// Template with `{key}` put before commit subjects.
+    fn commit_prefix(&self) -> &str;
+    /// Whether commits on ticket branches without the key are rejected.
+    fn commit_require_key(&self) -> bool;
+}
+
+impl CommitHooksConfig for Config {
+    fn commit_prefix(&self) -> &str {
+        &self.commit_prefix
+    }
+
+    fn commit_require_key(&self) -> bool {
+        self.commit_require_key
+    }
+}
+
+#[derive(Debug)]
+pub struct HooksConfig {
+    pub ticket_pattern: TicketPattern,
+    pub commit_prefix: String,
+    pub commit_require_key: bool,
+}
+
+impl TicketPatternConfig for HooksConfig {
+    fn ticket_pattern(&self) -> &TicketPattern {
+        &self.ticket_pattern
+    }
+}
+
+impl CommitHooksConfig for HooksConfig {
+    fn commit_prefix(&self) -> &str {
+        &self.commit_prefix
+    }
+
+    fn commit_require_key(&self) -> bool {
+        self.commit_require_key
+    }
+}
+
+/// Whether uncommitted changes are stashed before switching branches.
+pub trait AutostashConfig {
+    fn autostash(&self) -> bool;
+}
+
+impl AutostashConfig for Config {
+    fn autostash(&self) -> bool {
+        self.autostash
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// Where `pr checkout --worktree` adds worktrees.
+pub trait WorktreeConfig {
+    /// Template with `{repo}` and `{id}`, relative to the work tree.
+    fn worktree_path(&self) -> &str;
+}
+
+impl WorktreeConfig for Config {
+    fn worktree_path(&self) -> &str {
+        &self.worktree_path
+    }
+}
+
+pub trait PrsColumnsConfig {
+    fn prs_columns(&self) -> Option<&str>;
+}
+
+impl PrsColumnsConfig for Config {
+    fn prs_columns(&self) -> Option<&str> {
+        self.prs_columns.as_deref()
+    }
+}
+
+/// Approvals a PR needs on Bitbucket, which doesn't show its required approvers merge check
+/// to regular users.
+pub trait RequiredApprovalsConfig {
+    fn prs_required_approvals(&self) -> Option<u16>;
+}
+
+impl RequiredApprovalsConfig for Config {
+    fn prs_required_approvals(&self) -> Option<u16> {
+        self.prs_required_approvals
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Provider {
     BitBucket,
     GitLab,
@@ -87,12 +320,80 @@ impl fmt::Display for Provider {
     }
 }
 
+/// Where tickets live.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Tracker {
+    #[default]
+    Jira,
+    /// GitHub or GitLab issues of the repository
+    Issues,
+}
+
+impl FromStr for Tracker {
+    type Err = ConfigError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.to_lowercase().as_str() {
+            "jira" => Ok(Tracker::Jira),
+            "issues" => Ok(Tracker::Issues),
+            _ => Err(ConfigError::UnknownTracker(raw.to_string())),
+        }
+    }
+}
+
+/// How requests to Jira are authorized, Jira Server often has only basic auth.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum JiraAuth {
+    /// Personal access token sent as a bearer token
+    #[default]
+    Token,
+    /// Username and password
+    Basic,
+}
+
+impl FromStr for JiraAuth {
+    type Err = ConfigError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.to_lowercase().as_str() {
+            "token" => Ok(JiraAuth::Token),
+            "basic" => Ok(JiraAuth::Basic),
+            _ => Err(ConfigError::UnknownJiraAuth(raw.to_string())),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JiraPrLink {
+    /// A remote link updated in place, so the ticket shows the PR's latest state
+    RemoteLink,
+    /// A comment for every created or merged PR
+    Comment,
+}
+
+impl FromStr for JiraPrLink {
+    type Err = ConfigError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.to_lowercase().as_str() {
+            "link" => Ok(JiraPrLink::RemoteLink),
+            "comment" => Ok(JiraPrLink::Comment),
+            _ => Err(ConfigError::UnknownJiraPrLink(raw.to_string())),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ConfigError {
     ProviderNotSpecified,
     UnknownProvider(String),
     BaseUrlNotSpecified,
     InvalidBaseUrl(String),
+    InvalidTicketPattern(String),
+    UnknownJiraAuth(String),
+    UnknownJiraPrLink(String),
+    UnknownTracker(String),
+    IssuesNotSupported(Provider),
     UnableToUpdateConfig(String),
 }
 
@@ -123,6 +424,33 @@ impl fmt::Display for ConfigError {
                     "invalid base_url \"{value}\" is specified in .git/config",
                 )
             }
+            ConfigError::InvalidTicketPattern(value) => {
+                write!(
+                    f,
+                    "invalid ticketPattern \"{value}\" is specified in .git/config",
+                )
+            }
+            ConfigError::UnknownJiraAuth(value) => {
+                write!(
+                    f,
+                    "unknown jira.auth \"{value}\" is specified in .git/config, use token or basic",
+                )
+            }
+            ConfigError::UnknownJiraPrLink(value) => {
+                write!(
+                    f,
+                    "unknown jira.linkPrs \"{value}\" is specified in .git/config, use link or comment",
+                )
+            }
+            ConfigError::UnknownTracker(value) => {
+                write!(
+                    f,
+                    "unknown tracker \"{value}\" is specified in .git/config, use jira or issues",
+                )
+            }
+            ConfigError::IssuesNotSupported(provider) => {
+                write!(f, "{provider} has no issues, set gitext.tracker to jira")
+            }
             ConfigError::UnableToUpdateConfig(value) => {
                 write!(f, "unable to update config at .git/config: {value}")
             }
@@ -160,11 +488,111 @@ pub fn get_config(repo: &Repository) -> Result<Config, ConfigError> {
     let jira_url = config.get_string("gitext.jiraurl").ok();
     let jira_url = jira_url.and_then(|string| Url::parse(&string).ok());
 
+    let jira_auth = match config.get_string("gitext.jira.auth") {
+        Ok(auth) => or_default(JiraAuth::from_str(&auth), JiraAuth::default),
+        Err(_) => JiraAuth::default(),
+    };
+
+    let jira_on_pr_create = config.get_string("gitext.jira.onPrCreate").ok();
+    let jira_on_merge = config.get_string("gitext.jira.onMerge").ok();
+    // an empty value disables the transition
+    let jira_on_start = config
+        .get_string("gitext.jira.onStart")
+        .unwrap_or(DEFAULT_ON_START.to_string());
+    let jira_on_start = Some(jira_on_start).filter(|status| !status.is_empty());
+    let jira_on_close = config
+        .get_string("gitext.jira.onClose")
+        .unwrap_or(DEFAULT_ON_CLOSE.to_string());
+
+    let tracker = match config.get_string("gitext.tracker") {
+        Ok(tracker) => or_default(Tracker::from_str(&tracker), Tracker::default),
+        Err(_) => Tracker::default(),
+    };
+
+    let tracker = match (tracker, provider) {
+        (Tracker::Issues, Provider::BitBucket) => or_default(
+            Err(ConfigError::IssuesNotSupported(provider)),
+            Tracker::default,
+        ),
+        _ => tracker,
+    };
+
+    let jira_pr_link = match config.get_string("gitext.jira.linkPrs") {
+        Ok(link) => or_default(JiraPrLink::from_str(&link).map(Some), || None),
+        Err(_) => None,
+    };
+
+    let ticket_pattern = or_default(get_ticket_pattern_from_config(&config, tracker), || {
+        default_ticket_pattern(tracker)
+    });
+
+    let ticket_branch_template = config
+        .get_string("gitext.ticket.branchTemplate")
+        .unwrap_or(DEFAULT_BRANCH_TEMPLATE.to_string());
+    let ticket_base_branch = config.get_string("gitext.ticket.baseBranch").ok();
+    let ticket_assign_on_start = config.get_bool("gitext.ticket.assign").unwrap_or(false);
+    let tickets_jql = config
+        .get_string("gitext.tickets.jql")
+        .unwrap_or(DEFAULT_TICKETS_JQL.to_string());
+
+    let commit_prefix = get_commit_prefix_from_config(&config, tracker);
+    let commit_require_key = config.get_bool("gitext.hooks.requireKey").unwrap_or(false);
+
+    let autostash = config.get_bool("gitext.autostash").unwrap_or(false);
+    let worktree_path = config
+        .get_string("gitext.pr.worktreePath")
+        .unwrap_or(DEFAULT_WORKTREE_PATH.to_string());
+
+    let prs_columns = get_prs_columns_from_config(&config);
+    let prs_required_approvals = config
+        .get_i64("gitext.prs.requiredApprovals")
+        .ok()
+        .and_then(|approvals| u16::try_from(approvals).ok());
+
     Ok(Config {
         provider,
+        tracker,
         base_url,
         auth_domain,
         jira_url,
+        jira_auth,
+        jira_on_pr_create,
+        jira_on_merge,
+        jira_on_start,
+        jira_on_close,
+        jira_pr_link,
+        ticket_pattern,
+        ticket_branch_template,
+        ticket_base_branch,
+        ticket_assign_on_start,
+        tickets_jql,
+        commit_prefix,
+        commit_require_key,
+        autostash,
+        worktree_path,
+        prs_columns,
+        prs_required_approvals,
+    })
+}
+
+/// The part of the config `hooks run` needs, `None` when gitext doesn't handle the
+/// repository or the ticket pattern is invalid.
+///
+/// Hooks run on every commit, settings they don't use never make them fail.
+pub fn get_hooks_config(repo: &Repository) -> Option<HooksConfig> {
+    let config = repo.config().ok()?;
+    config.get_string("gitext.provider").ok()?;
+
+    let tracker = config
+        .get_string("gitext.tracker")
+        .ok()
+        .and_then(|tracker| Tracker::from_str(&tracker).ok())
+        .unwrap_or_default();
+
+    Some(HooksConfig {
+        ticket_pattern: get_ticket_pattern_from_config(&config, tracker).ok()?,
+        commit_prefix: get_commit_prefix_from_config(&config, tracker),
+        commit_require_key: config.get_bool("gitext.hooks.requireKey").unwrap_or(false),
     })
 }
 
@@ -208,6 +636,76 @@ pub fn set_provider(repo: &Repository, provider: Provider) -> Result<(), ConfigE
         .map_err(|err| ConfigError::UnableToUpdateConfig(err.message().to_string()))
 }
 
+/// What is behind a host for commands that run outside of a repository, from
+/// `gitext.host.<host>.provider`, `.baseUrl` and `.authDomain` of the global config.
+pub fn get_host_config(config: &GitConfig, host: &str) -> Result<Config, ConfigError> {
+    let section = format!("gitext.host.{host}");
+
+    let provider = config
+        .get_string(&format!("{section}.provider"))
+        .map_err(|_| ConfigError::ProviderNotSpecified)?;
+    let provider = Provider::from_str(&provider)?;
+
+    let base_url = config
+        .get_string(&format!("{section}.baseUrl"))
+        .unwrap_or(format!("https://{host}"));
+    let base_url = Url::parse(&base_url).map_err(|_| ConfigError::InvalidBaseUrl(base_url))?;
+
+    let auth_domain = config
+        .get_string(&format!("{section}.authDomain"))
+        .unwrap_or(host.to_string());
+
+    Ok(Config {
+        provider,
+        base_url,
+        auth_domain,
+        ..Config::default()
+    })
+}
+
+/// Hosts with a `gitext.host.<host>.provider`.
+pub fn get_configured_hosts(config: &GitConfig) -> Vec<String> {
+    let mut hosts = vec![];
+
+    if let Ok(mut entries) = config.entries(Some("gitext\\.host\\..*\\.provider")) {
+        while let Some(Ok(entry)) = entries.next() {
+            let host = entry
+                .name()
+                .and_then(|name| name.strip_prefix("gitext.host."))
+                .and_then(|name| name.strip_suffix(".provider"));
+
+            if let Some(host) = host {
+                if !hosts.iter().any(|known| known == host) {
+                    hosts.push(host.to_string());
+                }
+            }
+        }
+    }
+
+    hosts
+}
+
+/// Remembers the provider of the host of `host_config` in `config`, usually the global one.
+pub fn set_host_config(config: &mut GitConfig, host_config: &Config) -> Result<(), ConfigError> {
+    let host = host_config.base_url.host_str().unwrap_or_default();
+    let section = format!("gitext.host.{host}");
+
+    config
+        .set_str(
+            &format!("{section}.provider"),
+            &host_config.provider.to_string(),
+        )
+        .map_err(|err| ConfigError::UnableToUpdateConfig(err.message().to_string()))?;
+
+    config
+        .set_str(&format!("{section}.baseUrl"), host_config.base_url.as_str())
+        .map_err(|err| ConfigError::UnableToUpdateConfig(err.message().to_string()))?;
+
+    config
+        .set_str(&format!("{section}.authDomain"), &host_config.auth_domain)
+        .map_err(|err| ConfigError::UnableToUpdateConfig(err.message().to_string()))
+}
+
 pub fn get_aliases_from_config(config: &GitConfig) -> HashMap<String, String> {
     let mut aliases = HashMap::new();
     let mut entries = config.entries(Some("alias.*")).unwrap();
@@ -222,3 +720,210 @@ pub fn get_aliases_from_config(config: &GitConfig) -> HashMap<String, String> {
 
     aliases
 }
+
+pub fn get_prs_columns_from_config(config: &GitConfig) -> Option<String> {
+    config.get_string("gitext.prs.columns").ok()
+}
+
+fn get_commit_prefix_from_config(config: &GitConfig, tracker: Tracker) -> String {
+    config
+        .get_string("gitext.hooks.prefix")
+        .unwrap_or(match tracker {
+            Tracker::Jira => DEFAULT_COMMIT_PREFIX.to_string(),
+            Tracker::Issues => DEFAULT_ISSUE_COMMIT_PREFIX.to_string(),
+        })
+}
+
+/// Reads `gitext.ticketPattern` and the `gitext.ticketProjects` allowlist, e.g. `IOS, AND`.
+///
+/// Lowercase Jira keys are only matched by default for the projects of the allowlist.
+fn get_ticket_pattern_from_config(
+    config: &GitConfig,
+    tracker: Tracker,
+) -> Result<TicketPattern, ConfigError> {
+    let projects: Vec<String> = config
+        .get_string("gitext.ticketProjects")
+        .map(|projects| {
+            projects
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|project| !project.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let default = match tracker {
+        Tracker::Jira if projects.is_empty() => super::DEFAULT_TICKET_PATTERN,
+        Tracker::Jira => super::LENIENT_TICKET_PATTERN,
+        Tracker::Issues => super::DEFAULT_ISSUE_PATTERN,
+    };
+
+    let pattern = config
+        .get_string("gitext.ticketPattern")
+        .unwrap_or(default.to_string());
+
+    TicketPattern::new(&pattern, projects).map_err(|_| ConfigError::InvalidTicketPattern(pattern))
+}
+
+fn default_ticket_pattern(tracker: Tracker) -> TicketPattern {
+    match tracker {
+        Tracker::Jira => TicketPattern::default(),
+        Tracker::Issues => TicketPattern::new(super::DEFAULT_ISSUE_PATTERN, vec![]).unwrap(),
+    }
+}
+
+/// Optional settings with an invalid value fall back to their default, a typo in one of
+/// them shouldn't stop every command.
+fn or_default<T>(value: Result<T, ConfigError>, default: impl FnOnce() -> T) -> T {
+    value.unwrap_or_else(|err| {
+        eprintln!("warning: {err}; using the default");
+        default()
+    })
+}
+
+pub fn get_workspace_from_config(
+    config: &GitConfig,
+    group: Option<&str>,
+) -> (Vec<PathBuf>, Option<PathBuf>) {
+    let section = match group {
+        Some(group) => format!("gitext.workspace.{group}"),
+        None => String::from("gitext.workspace"),
+    };
+
+    let mut repos = vec![];
+
+    if let Ok(mut entries) = config.multivar(&format!("{section}.repo"), None) {
+        while let Some(Ok(entry)) = entries.next() {
+            if let Some(value) = entry.value() {
+                repos.push(expand_home(value));
+            }
+        }
+    }
+
+    let path = config.get_path(&format!("{section}.path")).ok();
+
+    (repos, path)
+}
+
+fn expand_home(value: &str) -> PathBuf {
+    match (value.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(relative), Some(home)) => home.join(relative),
+        _ => PathBuf::from(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_config(contents: &str) -> (PathBuf, GitConfig) {
+        let id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("gitext-config-{id}"));
+        fs::write(&path, contents).unwrap();
+
+        let config = GitConfig::open(&path).unwrap();
+        (path, config)
+    }
+
+    #[test]
+    fn workspace_from_config() {
+        let (path, config) = temp_config(
+            r#"
+[gitext "workspace"]
+    repo = /work/ios
+    repo = /work/android
+    path = /work
+[gitext "workspace.backend"]
+    repo = /work/api
+"#,
+        );
+
+        let (repos, dir) = get_workspace_from_config(&config, None);
+        assert_eq!(
+            repos,
+            vec![PathBuf::from("/work/ios"), PathBuf::from("/work/android")]
+        );
+        assert_eq!(dir, Some(PathBuf::from("/work")));
+
+        let (repos, dir) = get_workspace_from_config(&config, Some("backend"));
+        assert_eq!(repos, vec![PathBuf::from("/work/api")]);
+        assert_eq!(dir, None);
+
+        let (repos, dir) = get_workspace_from_config(&config, Some("unknown"));
+        assert!(repos.is_empty());
+        assert_eq!(dir, None);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn ticket_pattern_from_config() {
+        let (path, config) = temp_config(
+            r#"
+[gitext]
+    ticketProjects = IOS, AND
+"#,
+        );
+
+        let pattern = get_ticket_pattern_from_config(&config, Tracker::Jira).unwrap();
+        assert_eq!(
+            pattern.find("feature/WEB-1-IOS-2").as_deref(),
+            Some("IOS-2")
+        );
+        assert_eq!(pattern.find("fix/utf-8-and-3").as_deref(), Some("AND-3"));
+        fs::remove_file(path).unwrap();
+
+        let (path, config) = temp_config(
+            r#"
+[gitext]
+    ticketPattern = "(["
+"#,
+        );
+
+        assert!(matches!(
+            get_ticket_pattern_from_config(&config, Tracker::Jira),
+            Err(ConfigError::InvalidTicketPattern(_))
+        ));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn host_config() {
+        let (path, mut config) = temp_config(
+            r#"
+[gitext "host.gitlab.company.com"]
+    provider = gitlab
+    authDomain = company.com
+"#,
+        );
+
+        let gitlab = get_host_config(&config, "gitlab.company.com").unwrap();
+        assert!(matches!(gitlab.provider, Provider::GitLab));
+        assert_eq!(gitlab.base_url.as_str(), "https://gitlab.company.com/");
+        assert_eq!(gitlab.auth_domain, "company.com");
+
+        assert!(matches!(
+            get_host_config(&config, "bitbucket.company.com"),
+            Err(ConfigError::ProviderNotSpecified)
+        ));
+
+        let bitbucket = Config {
+            provider: Provider::BitBucket,
+            base_url: Url::parse("https://bitbucket.company.com").unwrap(),
+            auth_domain: "bitbucket.company.com".to_string(),
+            ..Config::default()
+        };
+        set_host_config(&mut config, &bitbucket).unwrap();
+
+        assert_eq!(
+            get_configured_hosts(&config),
+            vec!["gitlab.company.com", "bitbucket.company.com"]
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
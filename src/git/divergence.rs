@@ -0,0 +1,149 @@
+use git2::{BranchType, Error as GitError, Oid, Repository};
+
+/// How a local branch relates to the commit it's about to be switched to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BranchState {
+    UpToDate,
+    /// The target has commits the branch doesn't, the branch can be fast-forwarded.
+    Behind(usize),
+    /// The branch has commits that aren't pushed yet.
+    Ahead(usize),
+    Diverged {
+        ahead: usize,
+        behind: usize,
+    },
+}
+
+impl BranchState {
+    pub fn between(local: Oid, target: Oid, repo: &Repository) -> Result<BranchState, GitError> {
+        let state = match repo.graph_ahead_behind(local, target)? {
+            (0, 0) => BranchState::UpToDate,
+            (0, behind) => BranchState::Behind(behind),
+            (ahead, 0) => BranchState::Ahead(ahead),
+            (ahead, behind) => BranchState::Diverged { ahead, behind },
+        };
+
+        Ok(state)
+    }
+}
+
+/// A free name for keeping the commits of a diverged `branch`: `branch-local`, `branch-local-2`...
+pub fn backup_branch_name(branch: &str, repo: &Repository) -> String {
+    let mut name = format!("{branch}-local");
+    let mut index = 1;
+
+    while repo.find_branch(&name, BranchType::Local).is_ok() {
+        index += 1;
+        name = format!("{branch}-local-{index}");
+    }
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::switch_to_branch_with;
+    use crate::git::tests::TempRepo;
+    use crate::view::DivergedAction;
+
+    /// A `feature` branch at a local commit and `origin/feature` at a remote one, both on top
+    /// of a shared base commit.
+    fn feature(temp: &TempRepo, diverged: bool) -> (Oid, Oid) {
+        let base = temp.commit("base", &[]);
+        let local = if diverged {
+            temp.commit("local", &[base])
+        } else {
+            base
+        };
+        let remote = temp.commit("remote", &[base]);
+
+        let repo = &temp.repo;
+        repo.branch("feature", &repo.find_commit(local).unwrap(), false)
+            .unwrap();
+        repo.remote("origin", "git@github.com:chipp/gitext.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/feature", remote, false, "fetch")
+            .unwrap();
+
+        (local, remote)
+    }
+
+    fn switch(temp: &TempRepo, action: DivergedAction) {
+        let remote = temp.repo.find_remote("origin").unwrap();
+        switch_to_branch_with("feature", "", &remote, &temp.repo, |_, _, _, _| Ok(action)).unwrap();
+    }
+
+    fn branch_target(name: &str, repo: &Repository) -> Oid {
+        let branch = repo.find_branch(name, BranchType::Local).unwrap();
+        branch.get().peel_to_commit().unwrap().id()
+    }
+
+    #[test]
+    fn states() {
+        let temp = TempRepo::new();
+        let repo = &temp.repo;
+
+        let base = temp.commit("base", &[]);
+        let local = temp.commit("local", &[base]);
+        let remote = temp.commit("remote", &[base]);
+
+        let state = |local, target| BranchState::between(local, target, repo).unwrap();
+
+        assert_eq!(state(base, base), BranchState::UpToDate);
+        assert_eq!(state(base, remote), BranchState::Behind(1));
+        assert_eq!(state(local, base), BranchState::Ahead(1));
+        assert_eq!(
+            state(local, remote),
+            BranchState::Diverged {
+                ahead: 1,
+                behind: 1
+            }
+        );
+
+        repo.branch("fix-local", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        assert_eq!(backup_branch_name("fix", repo), "fix-local-2");
+        assert_eq!(backup_branch_name("feature", repo), "feature-local");
+    }
+
+    #[test]
+    fn fast_forwards_branch_behind() {
+        let temp = TempRepo::new();
+        let (_, remote) = feature(&temp, false);
+
+        switch(&temp, DivergedAction::Keep);
+
+        assert_eq!(branch_target("feature", &temp.repo), remote);
+        assert!(temp
+            .repo
+            .find_branch("feature-local", BranchType::Local)
+            .is_err());
+    }
+
+    #[test]
+    fn reset_keeps_unpushed_commits_in_backup() {
+        let temp = TempRepo::new();
+        let (local, remote) = feature(&temp, true);
+
+        switch(&temp, DivergedAction::Reset);
+
+        assert_eq!(branch_target("feature", &temp.repo), remote);
+        assert_eq!(branch_target("feature-local", &temp.repo), local);
+    }
+
+    #[test]
+    fn rename_keeps_unpushed_commits_in_backup() {
+        let temp = TempRepo::new();
+        let (local, remote) = feature(&temp, true);
+
+        switch(&temp, DivergedAction::Rename);
+
+        assert_eq!(branch_target("feature", &temp.repo), remote);
+        assert_eq!(branch_target("feature-local", &temp.repo), local);
+
+        let feature = temp.repo.find_branch("feature", BranchType::Local).unwrap();
+        let upstream = feature.upstream().unwrap();
+        assert_eq!(upstream.name().unwrap(), Some("origin/feature"));
+    }
+}
@@ -1,8 +1,9 @@
-use git2::{Branch, BranchType, ErrorClass, ErrorCode, Remote, Repository};
+use git2::{Branch, BranchType, ErrorClass, ErrorCode, Oid, Remote, Repository};
 
 use crate::error::Error;
 use crate::exec_git_cmd;
-use crate::git::find_remote_branch;
+use crate::git::{backup_branch_name, find_remote_branch, BranchState};
+use crate::view::{self, DivergedAction};
 
 pub fn switch_to_branch(
     branch_name: &str,
@@ -10,11 +11,38 @@ pub fn switch_to_branch(
     remote: &Remote,
     repo: &Repository,
 ) -> Result<(), Error> {
-    match find_remote_branch(branch_name, &remote, &repo) {
-        Ok(remote_branch) => switch_to_existing_branch(remote_branch, branch_name, repo),
+    switch_to_branch_with(branch_name, commit, remote, repo, view::ask_diverged)
+}
+
+/// `switch_to_branch` with `resolve` deciding what happens to a diverged local branch, it
+/// gets the branch, the commits it's ahead and behind and the name of its backup.
+pub fn switch_to_branch_with<F>(
+    branch_name: &str,
+    commit: &str,
+    remote: &Remote,
+    repo: &Repository,
+    resolve: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&str, usize, usize, &str) -> Result<DivergedAction, Error>,
+{
+    match find_remote_branch(branch_name, remote, repo) {
+        Ok(remote_branch) => switch_to_existing_branch(remote_branch, branch_name, repo, resolve),
         Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
-            exec_git_cmd(["branch", branch_name, commit], Some(repo))?;
-            switch_to_local_branch(branch_name, &repo)
+            match repo.find_branch(branch_name, BranchType::Local) {
+                Ok(local_branch) => {
+                    let target = Oid::from_str(commit)?;
+                    update_local_branch(local_branch, target, commit, None, repo, resolve)
+                }
+                Err(err)
+                    if err.class() == ErrorClass::Reference
+                        && err.code() == ErrorCode::NotFound =>
+                {
+                    exec_git_cmd(["branch", branch_name, commit], Some(repo))?;
+                    switch_to_local_branch(branch_name, repo)
+                }
+                Err(err) => Err(err.into()),
+            }
         }
         Err(err) => Err(err.into()),
     }
@@ -23,24 +51,104 @@ pub fn switch_to_branch(
 fn switch_to_local_branch(branch_name: &str, repo: &Repository) -> Result<(), Error> {
     println!("switching to local branch {}", branch_name);
 
-    exec_git_cmd(["switch", branch_name], Some(&repo))
+    exec_git_cmd(["switch", branch_name], Some(repo))
 }
 
-fn switch_to_existing_branch(
+fn switch_to_existing_branch<F>(
     remote_branch: Branch,
     branch_name: &str,
     repo: &Repository,
-) -> Result<(), Error> {
+    resolve: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&str, usize, usize, &str) -> Result<DivergedAction, Error>,
+{
     let remote_branch_name = remote_branch.name()?.unwrap();
 
     match repo.find_branch(branch_name, BranchType::Local) {
-        Ok(_) => switch_to_local_branch(branch_name, &repo),
+        Ok(local_branch) => {
+            let target = remote_branch.get().peel_to_commit()?.id();
+            update_local_branch(
+                local_branch,
+                target,
+                remote_branch_name,
+                Some(remote_branch_name),
+                repo,
+                resolve,
+            )
+        }
         Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
             println!("switching to remote branch {}", branch_name);
 
             exec_git_cmd(["branch", branch_name, remote_branch_name], Some(repo))?;
-            exec_git_cmd(["switch", branch_name], Some(&repo))
+            exec_git_cmd(["switch", branch_name], Some(repo))
         }
         Err(err) => Err(err.into()),
     }
 }
+
+/// Brings an existing local branch to `target` and switches to it without losing its
+/// unpushed commits.
+fn update_local_branch<F>(
+    local_branch: Branch,
+    target: Oid,
+    target_name: &str,
+    upstream: Option<&str>,
+    repo: &Repository,
+    resolve: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&str, usize, usize, &str) -> Result<DivergedAction, Error>,
+{
+    let branch_name = local_branch.name()?.unwrap_or_default().to_string();
+    let local = local_branch.get().peel_to_commit()?.id();
+    let is_head = local_branch.is_head();
+
+    match BranchState::between(local, target, repo)? {
+        BranchState::UpToDate => switch_to_local_branch(&branch_name, repo),
+        BranchState::Ahead(ahead) => {
+            println!("keeping {ahead} unpushed commit(s) of local branch {branch_name}");
+            switch_to_local_branch(&branch_name, repo)
+        }
+        BranchState::Behind(behind) => {
+            println!("fast-forwarding local branch {branch_name} by {behind} commit(s)");
+
+            switch_to_local_branch(&branch_name, repo)?;
+            exec_git_cmd(["merge", "--ff-only", "--quiet", target_name], Some(repo))
+        }
+        BranchState::Diverged { ahead, behind } => {
+            let backup = backup_branch_name(&branch_name, repo);
+
+            match resolve(&branch_name, ahead, behind, &backup)? {
+                DivergedAction::Keep => switch_to_local_branch(&branch_name, repo),
+                DivergedAction::Reset => {
+                    exec_git_cmd(["branch", &backup, &branch_name], Some(repo))?;
+                    println!("saved the commits of {branch_name} to {backup}");
+
+                    if is_head {
+                        // unlike --hard, --keep refuses to drop uncommitted changes
+                        exec_git_cmd(["reset", "--keep", target_name], Some(repo))
+                    } else {
+                        exec_git_cmd(["branch", "--force", &branch_name, target_name], Some(repo))?;
+                        switch_to_local_branch(&branch_name, repo)
+                    }
+                }
+                DivergedAction::Rename => {
+                    exec_git_cmd(["branch", "--move", &branch_name, &backup], Some(repo))?;
+                    println!("renamed local branch {branch_name} to {backup}");
+
+                    exec_git_cmd(["branch", &branch_name, target_name], Some(repo))?;
+
+                    if let Some(upstream) = upstream {
+                        exec_git_cmd(
+                            ["branch", "--set-upstream-to", upstream, &branch_name],
+                            Some(repo),
+                        )?;
+                    }
+
+                    switch_to_local_branch(&branch_name, repo)
+                }
+            }
+        }
+    }
+}
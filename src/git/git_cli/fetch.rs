@@ -1,10 +1,11 @@
-use crate::git::{exec_git_cmd, AuthDomainConfig};
+use crate::git::{exec_git_cmd, AuthDomainConfig, FetchSpec};
 use crate::Error;
 
 use git2::{Remote, Repository};
 
 pub fn fetch_remote<Conf>(
     remote: &mut Remote,
+    spec: FetchSpec,
     repo: &Repository,
     _config: &Conf,
 ) -> Result<(), Error>
@@ -14,5 +15,14 @@ where
     let remote_name = remote.name().unwrap();
     println!("fetching remote {}", remote_name);
 
-    exec_git_cmd(["fetch", remote_name], Some(repo))
+    let mut args = vec!["fetch".to_string()];
+
+    if spec.prune {
+        args.push("--prune".to_string());
+    }
+
+    args.push(remote_name.to_string());
+    args.extend(spec.refspecs(remote_name));
+
+    exec_git_cmd(args, Some(repo))
 }
@@ -0,0 +1,43 @@
+use crate::git::{git_command, AuthDomainConfig};
+use crate::Error;
+
+use git2::{Remote, Repository};
+
+pub fn push_branch<Conf>(
+    remote: &mut Remote,
+    branch: &str,
+    set_upstream: bool,
+    force_with_lease: bool,
+    repo: &Repository,
+    _config: &Conf,
+) -> Result<(), Error>
+where
+    Conf: AuthDomainConfig,
+{
+    let remote_name = remote.name().unwrap();
+    println!("pushing {branch} to {remote_name}");
+
+    let mut args = vec!["push".to_string()];
+
+    if set_upstream {
+        args.push("--set-upstream".to_string());
+    }
+
+    if force_with_lease {
+        args.push(format!("--force-with-lease=refs/heads/{branch}"));
+    }
+
+    args.push(remote_name.to_string());
+    args.push(format!("refs/heads/{branch}:refs/heads/{branch}"));
+
+    // git explains why the push failed
+    let status = git_command(args, Some(repo))
+        .status()
+        .map_err(Error::FailedToExecuteGit)?;
+
+    if !status.success() {
+        return Err(Error::PushFailed(branch.to_string()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use git2::Repository;
+
+use crate::error::Error;
+use crate::git::exec_git_cmd;
+
+pub fn stash_changes(message: &str, repo: &Repository) -> Result<(), Error> {
+    exec_git_cmd(
+        ["stash", "push", "--include-untracked", "--message", message],
+        Some(repo),
+    )
+}
+
+pub fn apply_stash(index: usize, repo: &Repository) -> Result<(), Error> {
+    exec_git_cmd(["stash", "pop", &format!("stash@{{{index}}}")], Some(repo))
+}
@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+
+use super::TicketPattern;
+
+/// Hooks that call back into gitext.
+pub const HOOKS: &[&str] = &["prepare-commit-msg", "commit-msg"];
+
+/// First line of the hooks gitext writes, tells them apart from the ones it chains.
+const MARKER: &str = "# installed by gitext";
+
+/// Directory git runs hooks from, `core.hooksPath` or `.git/hooks`.
+pub fn get_hooks_dir(repo: &Repository) -> PathBuf {
+    let hooks_path = repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"));
+
+    match hooks_path {
+        // relative paths are relative to where git runs hooks, the root of the work tree
+        Ok(path) if path.is_relative() => repo.workdir().unwrap_or(repo.path()).join(path),
+        Ok(path) => path,
+        Err(_) => repo.path().join("hooks"),
+    }
+}
+
+/// Where a hook that existed before `hooks install` is moved to be chained.
+pub fn chained_hook_path(hook: &Path) -> PathBuf {
+    let mut name = hook.file_name().unwrap_or_default().to_os_string();
+    name.push(".local");
+
+    hook.with_file_name(name)
+}
+
+pub fn is_gitext_hook(contents: &str) -> bool {
+    contents.lines().nth(1) == Some(MARKER)
+}
+
+/// A hook that runs the chained hook first and then `gitext hooks run <name>`.
+pub fn hook_script(name: &str, gitext: &Path) -> String {
+    format!(
+        r#"#!/bin/sh
+{MARKER}
+chained="$(dirname "$0")/{name}.local"
+if [ -x "$chained" ]; then
+    "$chained" "$@" || exit $?
+fi
+
+gitext="{gitext}"
+if [ -x "$gitext" ]; then
+    exec "$gitext" hooks run {name} "$@"
+fi
+"#,
+        gitext = gitext.display()
+    )
+}
+
+/// The first line of `message` that isn't a comment.
+pub fn message_subject(message: &str) -> Option<&str> {
+    message.lines().find(|line| !line.starts_with('#'))
+}
+
+/// `message` with `prefix` before its subject, or `None` when the subject references `key`.
+///
+/// An empty subject gets the prefix too, so it's already there when the editor opens.
+pub fn prefix_message(
+    message: &str,
+    prefix: &str,
+    key: &str,
+    pattern: &TicketPattern,
+) -> Option<String> {
+    let mut lines = message.lines().collect::<Vec<_>>();
+    let subject = lines.iter().position(|line| !line.starts_with('#'));
+
+    let prefixed = match subject {
+        Some(index) if pattern.references(lines[index], key) => return None,
+        Some(index) => {
+            let subject = format!("{prefix}{}", lines[index]);
+            lines[index] = &subject;
+            lines.join("\n")
+        }
+        None => {
+            lines.insert(0, prefix);
+            lines.join("\n")
+        }
+    };
+
+    Some(prefixed + "\n")
+}
+
+/// Whether the message has nothing but the prefix `prepare-commit-msg` put into it.
+pub fn is_only_prefix(message: &str, prefix: &str) -> bool {
+    let text = message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    text.trim() == prefix.trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix() {
+        let pattern = TicketPattern::default();
+
+        assert_eq!(
+            prefix_message("Fix crash\n\nDetails\n", "IOS-1: ", "IOS-1", &pattern).as_deref(),
+            Some("IOS-1: Fix crash\n\nDetails\n")
+        );
+        assert_eq!(
+            prefix_message("IOS-1 Fix crash\n", "IOS-1: ", "IOS-1", &pattern),
+            None
+        );
+        assert_eq!(
+            prefix_message(
+                "\n# Please enter the commit message\n",
+                "IOS-1: ",
+                "IOS-1",
+                &pattern
+            )
+            .as_deref(),
+            Some("IOS-1: \n# Please enter the commit message\n")
+        );
+        assert_eq!(
+            prefix_message("# Comment only\n", "IOS-1: ", "IOS-1", &pattern).as_deref(),
+            Some("IOS-1: \n# Comment only\n")
+        );
+    }
+
+    #[test]
+    fn only_prefix() {
+        assert!(is_only_prefix("IOS-1: \n# Please enter\n", "IOS-1: "));
+        assert!(!is_only_prefix("IOS-1: Fix crash\n", "IOS-1: "));
+    }
+
+    #[test]
+    fn script() {
+        let script = hook_script("commit-msg", Path::new("/usr/local/bin/gitext"));
+
+        assert!(is_gitext_hook(&script));
+        assert!(script.contains("exec \"$gitext\" hooks run commit-msg \"$@\""));
+        assert!(!is_gitext_hook("#!/bin/sh\nexit 0\n"));
+    }
+
+    #[test]
+    fn chained_path() {
+        assert_eq!(
+            chained_hook_path(Path::new("/repo/.git/hooks/commit-msg")),
+            Path::new("/repo/.git/hooks/commit-msg.local")
+        );
+    }
+}
@@ -1,8 +1,10 @@
-use crate::git::find_remote_branch;
+use crate::git::{backup_branch_name, find_remote_branch, BranchState};
+use crate::view::{self, DivergedAction};
 use crate::Error;
 
 use git2::{
-    build::CheckoutBuilder, Branch, BranchType, ErrorClass, ErrorCode, Oid, Remote, Repository,
+    build::CheckoutBuilder, Branch, BranchType, Commit, ErrorClass, ErrorCode, Oid, Remote,
+    Repository,
 };
 
 pub fn switch_to_branch(
@@ -11,44 +13,133 @@ pub fn switch_to_branch(
     remote: &Remote,
     repo: &Repository,
 ) -> Result<(), Error> {
-    match find_remote_branch(branch_name, &remote, &repo) {
-        Ok(remote_branch) => switch_to_existing_branch(branch_name, remote_branch, repo),
+    switch_to_branch_with(branch_name, commit, remote, repo, view::ask_diverged)
+}
+
+/// `switch_to_branch` with `resolve` deciding what happens to a diverged local branch, it
+/// gets the branch, the commits it's ahead and behind and the name of its backup.
+pub fn switch_to_branch_with<F>(
+    branch_name: &str,
+    commit: &str,
+    remote: &Remote,
+    repo: &Repository,
+    resolve: F,
+) -> Result<(), Error>
+where
+    F: FnOnce(&str, usize, usize, &str) -> Result<DivergedAction, Error>,
+{
+    let remote_branch = match find_remote_branch(branch_name, remote, repo) {
+        Ok(remote_branch) => Some(remote_branch),
         Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
-            // TODO: handle existing local branch
-            let id = Oid::from_str(commit)?;
-            let commit = repo.find_commit(id)?;
+            None
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let target = match &remote_branch {
+        Some(remote_branch) => remote_branch.get().peel_to_commit()?,
+        None => repo.find_commit(Oid::from_str(commit)?)?,
+    };
 
-            let local_branch = repo.branch(branch_name, &commit, false)?;
-            switch_to_local_branch(local_branch, &repo)
+    let local_branch = match repo.find_branch(branch_name, BranchType::Local) {
+        Ok(local_branch) => {
+            update_local_branch(local_branch, &target, remote_branch, repo, resolve)?
         }
-        Err(err) => Err(err.into()),
-    }
+        Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
+            create_local_branch(branch_name, &target, remote_branch, repo)?
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    switch_to_local_branch(local_branch, repo)
 }
 
-fn switch_to_existing_branch(
+fn create_local_branch<'r>(
     branch_name: &str,
-    remote_branch: Branch,
-    repo: &Repository,
-) -> Result<(), Error> {
-    match repo.find_branch(branch_name, BranchType::Local) {
-        Ok(local_branch) => switch_to_local_branch(local_branch, &repo),
-        Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
-            println!(
-                "creating a local branch from remote branch {}",
-                remote_branch.name().unwrap().unwrap()
-            );
+    target: &Commit,
+    remote_branch: Option<Branch>,
+    repo: &'r Repository,
+) -> Result<Branch<'r>, Error> {
+    let mut local_branch = repo.branch(branch_name, target, false)?;
 
-            let commit = remote_branch.get().peel_to_commit()?;
+    if let Some(remote_branch) = remote_branch {
+        println!(
+            "creating a local branch from remote branch {}",
+            remote_branch.name().unwrap().unwrap()
+        );
 
-            let mut local_branch = repo.branch(branch_name, &commit, false)?;
-            local_branch.set_upstream(remote_branch.name().unwrap())?;
+        local_branch.set_upstream(remote_branch.name().unwrap())?;
+    }
+
+    Ok(local_branch)
+}
+
+/// Brings an existing local branch to `target` without losing its unpushed commits.
+fn update_local_branch<'r, F>(
+    mut local_branch: Branch<'r>,
+    target: &Commit,
+    remote_branch: Option<Branch>,
+    repo: &'r Repository,
+    resolve: F,
+) -> Result<Branch<'r>, Error>
+where
+    F: FnOnce(&str, usize, usize, &str) -> Result<DivergedAction, Error>,
+{
+    let branch_name = local_branch.name()?.unwrap_or_default().to_string();
+    let local = local_branch.get().peel_to_commit()?;
 
-            switch_to_local_branch(local_branch, &repo)
+    match BranchState::between(local.id(), target.id(), repo)? {
+        BranchState::UpToDate => Ok(local_branch),
+        BranchState::Ahead(ahead) => {
+            println!("keeping {ahead} unpushed commit(s) of local branch {branch_name}");
+            Ok(local_branch)
+        }
+        BranchState::Behind(behind) => {
+            println!("fast-forwarding local branch {branch_name} by {behind} commit(s)");
+            move_branch(local_branch, target, "fast-forward", repo)
+        }
+        BranchState::Diverged { ahead, behind } => {
+            let backup = backup_branch_name(&branch_name, repo);
+
+            match resolve(&branch_name, ahead, behind, &backup)? {
+                DivergedAction::Keep => Ok(local_branch),
+                DivergedAction::Reset => {
+                    repo.branch(&backup, &local, false)?;
+                    println!("saved the commits of {branch_name} to {backup}");
+
+                    move_branch(local_branch, target, "reset", repo)
+                }
+                DivergedAction::Rename => {
+                    local_branch.rename(&backup, false)?;
+                    println!("renamed local branch {branch_name} to {backup}");
+
+                    create_local_branch(&branch_name, target, remote_branch, repo)
+                }
+            }
         }
-        Err(err) => Err(err.into()),
     }
 }
 
+fn move_branch<'r>(
+    mut branch: Branch<'r>,
+    target: &Commit,
+    action: &str,
+    repo: &'r Repository,
+) -> Result<Branch<'r>, Error> {
+    // the work tree of the checked out branch has to move along with it
+    if branch.is_head() {
+        let mut checkout_builder = CheckoutBuilder::new();
+        checkout_builder.safe();
+
+        repo.checkout_tree(target.as_object(), Some(&mut checkout_builder))?;
+    }
+
+    let reflog = format!("gitext: {action} to {}", target.id());
+    let reference = branch.get_mut().set_target(target.id(), &reflog)?;
+
+    Ok(Branch::wrap(reference))
+}
+
 fn switch_to_local_branch(branch: Branch, repo: &Repository) -> Result<(), Error> {
     println!(
         "switching to local branch {}",
@@ -62,7 +153,7 @@ fn switch_to_local_branch(branch: Branch, repo: &Repository) -> Result<(), Error
     checkout_builder.safe();
 
     repo.checkout_tree(commit.as_object(), Some(&mut checkout_builder))?;
-    repo.set_head(&reference.name().unwrap())?;
+    repo.set_head(reference.name().unwrap())?;
 
     Ok(())
 }
@@ -1,14 +1,10 @@
-mod formatter;
-mod reporter;
-
 use std::cell::RefCell;
-use std::io::{self, Write};
 use std::path::Path;
 
 use super::credential_helper::CredentialHelper;
+use super::progress::{report_sideband, report_transfer, State};
 use crate::git::AuthDomainConfig;
 use crate::Error;
-use reporter::{report_clone, State};
 
 use git2::build::RepoBuilder;
 use git2::Repository;
@@ -17,30 +13,38 @@ pub fn clone_repo<Conf>(url: &str, path: &Path, config: &Conf) -> Result<Reposit
 where
     Conf: AuthDomainConfig,
 {
-    let mut credential_helper = CredentialHelper::new();
+    let git_config = git2::Config::open_default().ok();
+    let credential_helper = CredentialHelper::new(url, git_config.as_ref());
+    let connect_url = credential_helper.connect_url().map(String::from);
+    let credential_helper = RefCell::new(credential_helper);
 
     let state = RefCell::new(State::new());
 
     let mut callbacks = git2::RemoteCallbacks::new();
-    callbacks.credentials(move |url, username_from_url, allowed_types| {
+    callbacks.credentials(|url, username_from_url, allowed_types| {
+        let mut credential_helper = credential_helper.borrow_mut();
         credential_helper.credentials(url, username_from_url, allowed_types, config)
     });
     callbacks.transfer_progress(|stats| {
         let mut state = state.borrow_mut();
-        report_clone(&mut *state, stats.to_owned());
-        true
-    });
-    callbacks.sideband_progress(|text| {
-        if let Ok(text) = std::str::from_utf8(text) {
-            eprint!("remote: {text}");
-            io::stderr().flush().unwrap();
-        }
+        report_transfer(&mut state, stats.to_owned());
         true
     });
+    callbacks.sideband_progress(report_sideband);
 
     let mut fo = git2::FetchOptions::new();
     fo.remote_callbacks(callbacks);
 
-    let repo = RepoBuilder::new().fetch_options(fo).clone(url, path)?;
+    let repo = RepoBuilder::new()
+        .fetch_options(fo)
+        .clone(connect_url.as_deref().unwrap_or(url), path)?;
+
+    credential_helper.borrow().approve();
+
+    // origin keeps the url of the ssh alias, like `git clone` would leave it
+    if connect_url.is_some() {
+        repo.remote_set_url("origin", url)?;
+    }
+
     Ok(repo)
 }
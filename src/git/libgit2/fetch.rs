@@ -1,28 +1,204 @@
-use git2::{Remote, RemoteCallbacks, Repository};
+use std::cell::RefCell;
+
+use git2::{FetchPrune, Oid, Remote, RemoteCallbacks, Repository};
 
 use super::credential_helper::CredentialHelper;
-use crate::{error::Error, git::AuthDomainConfig};
+use super::progress::{report_sideband, report_transfer, State};
+use crate::{
+    error::Error,
+    git::{AuthDomainConfig, FetchSpec},
+};
 
 pub fn fetch_remote<Conf>(
     remote: &mut Remote,
-    _repo: &Repository,
+    spec: FetchSpec,
+    repo: &Repository,
     config: &Conf,
 ) -> Result<(), Error>
 where
     Conf: AuthDomainConfig,
 {
-    println!("fetching remote {}", remote.name().unwrap());
+    let remote_name = remote.name().unwrap().to_string();
+    println!("fetching remote {}", remote_name);
+
+    let url = remote.url().unwrap_or_default().to_string();
+    let credential_helper = CredentialHelper::new(&url, repo.config().ok().as_ref());
+
+    // libgit2 connects to the host of the url as is, an ssh alias is reached through an
+    // anonymous remote with the refspecs of the named one
+    let mut alias = match credential_helper.connect_url() {
+        Some(connect_url) => Some(repo.remote_anonymous(connect_url)?),
+        None => None,
+    };
+    let credential_helper = RefCell::new(credential_helper);
+
+    let mut refspecs = spec.refspecs(&remote_name);
+    if refspecs.is_empty() && alias.is_some() {
+        refspecs = remote
+            .fetch_refspecs()?
+            .iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+    }
 
-    let mut credential_helper = CredentialHelper::new();
+    let state = RefCell::new(State::new());
+    let updates = RefCell::new(Vec::new());
 
     let mut callbacks = RemoteCallbacks::new();
-    callbacks.credentials(move |url, username_from_url, allowed_types| {
+    callbacks.credentials(|url, username_from_url, allowed_types| {
+        let mut credential_helper = credential_helper.borrow_mut();
         credential_helper.credentials(url, username_from_url, allowed_types, config)
     });
+    callbacks.transfer_progress(|stats| {
+        let mut state = state.borrow_mut();
+        report_transfer(&mut state, stats.to_owned());
+        true
+    });
+    callbacks.sideband_progress(report_sideband);
+    callbacks.update_tips(|name, old, new| {
+        updates.borrow_mut().push(RefUpdate {
+            name: name.to_string(),
+            old,
+            new,
+        });
+        true
+    });
 
     let mut fo = git2::FetchOptions::new();
     fo.remote_callbacks(callbacks);
 
-    remote.fetch::<&str>(&[], Some(&mut fo), None)?;
+    if spec.prune {
+        fo.prune(FetchPrune::On);
+    }
+
+    match alias.as_mut() {
+        Some(alias) => alias.fetch(&refspecs, Some(&mut fo), None)?,
+        None => remote.fetch(&refspecs, Some(&mut fo), None)?,
+    }
+
+    credential_helper.borrow().approve();
+
+    let updates = updates.borrow();
+    if !updates.is_empty() {
+        eprintln!("From {url}");
+
+        let width = updates
+            .iter()
+            .map(|update| update.source(&remote_name).len())
+            .max()
+            .unwrap_or_default();
+
+        for update in updates.iter() {
+            let forced = !update.old.is_zero()
+                && !update.new.is_zero()
+                && !repo
+                    .graph_descendant_of(update.new, update.old)
+                    .unwrap_or(false);
+
+            eprintln!("{}", update.line(&remote_name, forced, width));
+        }
+    }
+
     Ok(())
 }
+
+/// A ref the fetch created, moved or pruned.
+struct RefUpdate {
+    name: String,
+    old: Oid,
+    new: Oid,
+}
+
+impl RefUpdate {
+    /// The ref like `git fetch` shows it, `origin/main` or `v1.0`.
+    fn destination(&self) -> &str {
+        self.name
+            .strip_prefix("refs/remotes/")
+            .or_else(|| self.name.strip_prefix("refs/tags/"))
+            .unwrap_or(&self.name)
+    }
+
+    /// Name of the ref on the remote, `main` for `origin/main`.
+    fn source(&self, remote_name: &str) -> &str {
+        if self.new.is_zero() {
+            return "(none)";
+        }
+
+        let destination = self.destination();
+
+        destination
+            .strip_prefix(remote_name)
+            .and_then(|name| name.strip_prefix('/'))
+            .unwrap_or(destination)
+    }
+
+    /// A line of the `git fetch` report, like `   3f2a1c9..8b0e4d2  main -> origin/main`.
+    fn line(&self, remote_name: &str, forced: bool, width: usize) -> String {
+        let short = |oid: Oid| oid.to_string()[..7].to_string();
+
+        let (flag, summary) = if self.old.is_zero() {
+            let kind = if self.name.starts_with("refs/tags/") {
+                "[new tag]"
+            } else if self.name.starts_with("refs/remotes/") {
+                "[new branch]"
+            } else {
+                "[new ref]"
+            };
+
+            ('*', kind.to_string())
+        } else if self.new.is_zero() {
+            ('-', "[deleted]".to_string())
+        } else if forced {
+            ('+', format!("{}...{}", short(self.old), short(self.new)))
+        } else {
+            (' ', format!("{}..{}", short(self.old), short(self.new)))
+        };
+
+        let suffix = if forced { "  (forced update)" } else { "" };
+
+        format!(
+            " {flag} {summary:<17} {source:<width$} -> {destination}{suffix}",
+            source = self.source(remote_name),
+            destination = self.destination(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_lines() {
+        let old = Oid::from_str("3f2a1c9e7d6b5a4f3e2d1c0b9a8f7e6d5c4b3a29").unwrap();
+        let new = Oid::from_str("8b0e4d2f1a3c5e7b9d0f2a4c6e8b0d2f4a6c8e01").unwrap();
+
+        let update = |name: &str, old, new| RefUpdate {
+            name: name.to_string(),
+            old,
+            new,
+        };
+
+        assert_eq!(
+            update("refs/remotes/origin/main", old, new).line("origin", false, 4),
+            "   3f2a1c9..8b0e4d2  main -> origin/main"
+        );
+        assert_eq!(
+            update("refs/remotes/origin/feature", old, new).line("origin", true, 7),
+            " + 3f2a1c9...8b0e4d2 feature -> origin/feature  (forced update)"
+        );
+        assert_eq!(
+            update("refs/remotes/origin/fix", Oid::zero(), new).line("origin", false, 6),
+            " * [new branch]      fix    -> origin/fix"
+        );
+        assert_eq!(
+            update("refs/tags/v1.0", Oid::zero(), new).line("origin", false, 4),
+            " * [new tag]         v1.0 -> v1.0"
+        );
+        assert_eq!(
+            update("refs/remotes/origin/old", old, Oid::zero()).line("origin", false, 6),
+            " - [deleted]         (none) -> origin/old"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use url::Url;
+
+/// Credentials of an http remote kept by the `credential.helper`s of a git config, asked
+/// for and stored the way `git credential fill`, `approve` and `reject` do.
+pub struct GitCredential {
+    helpers: Vec<String>,
+    description: String,
+    username: Option<String>,
+}
+
+impl GitCredential {
+    /// `None` when `url` isn't an http one or no helper is configured for it.
+    pub fn new(
+        url: &str,
+        username: Option<&str>,
+        git_config: &git2::Config,
+    ) -> Option<GitCredential> {
+        let url = Url::parse(url).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+
+        let helpers = configured_helpers(&url, git_config);
+        if helpers.is_empty() {
+            return None;
+        }
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{port}", url.host_str()?),
+            None => url.host_str()?.to_string(),
+        };
+
+        let mut description = format!("protocol={}\nhost={host}\n", url.scheme());
+
+        if git_config
+            .get_bool("credential.useHttpPath")
+            .unwrap_or(false)
+        {
+            let path = url.path().trim_start_matches('/');
+            description.push_str(&format!("path={path}\n"));
+        }
+
+        let username = username
+            .or(Some(url.username()))
+            .filter(|user| !user.is_empty())
+            .map(String::from);
+
+        Some(GitCredential {
+            helpers,
+            description,
+            username,
+        })
+    }
+
+    /// Asks the helpers in order until one knows both the username and the password.
+    pub fn fill(&self) -> Option<(String, String)> {
+        let mut username = self.username.clone();
+        let mut password = None;
+
+        for helper in &self.helpers {
+            let mut input = self.description.clone();
+            if let Some(username) = &username {
+                input.push_str(&format!("username={username}\n"));
+            }
+
+            let output = match run_helper(helper, "get", &input) {
+                Some(output) => output,
+                None => continue,
+            };
+
+            let mut quit = false;
+
+            for line in output.lines() {
+                match line.split_once('=') {
+                    Some(("username", value)) => username = Some(value.to_string()),
+                    Some(("password", value)) => password = Some(value.to_string()),
+                    Some(("quit", value)) => quit = matches!(value, "1" | "true"),
+                    _ => {}
+                }
+            }
+
+            if password.is_some() || quit {
+                break;
+            }
+        }
+
+        username.zip(password)
+    }
+
+    /// Lets the helpers store credentials the remote accepted.
+    pub fn approve(&self, username: &str, password: &str) {
+        self.send("store", username, password);
+    }
+
+    /// Lets the helpers forget credentials the remote rejected.
+    pub fn reject(&self, username: &str, password: &str) {
+        self.send("erase", username, password);
+    }
+
+    fn send(&self, action: &str, username: &str, password: &str) {
+        let input = format!(
+            "{}username={username}\npassword={password}\n",
+            self.description
+        );
+
+        for helper in &self.helpers {
+            run_helper(helper, action, &input);
+        }
+    }
+}
+
+/// Values of `credential.helper` and of `credential.<url>.helper` for urls matching `url`,
+/// in the order git runs them, an empty value clears the ones before it.
+fn configured_helpers(url: &Url, git_config: &git2::Config) -> Vec<String> {
+    let mut helpers = Vec::new();
+
+    let mut entries = match git_config.entries(Some("credential\\..*helper")) {
+        Ok(entries) => entries,
+        Err(_) => return helpers,
+    };
+
+    while let Some(Ok(entry)) = entries.next() {
+        let name = entry.name().unwrap_or_default();
+        let applies = match name
+            .strip_prefix("credential.")
+            .and_then(|name| name.strip_suffix("helper"))
+        {
+            Some("") => true,
+            Some(pattern) => pattern
+                .strip_suffix('.')
+                .map(|pattern| url_matches(pattern, url))
+                .unwrap_or(false),
+            None => false,
+        };
+
+        if applies {
+            match entry.value() {
+                Some("") | None => helpers.clear(),
+                Some(helper) => helpers.push(helper.to_string()),
+            }
+        }
+    }
+
+    helpers
+}
+
+/// Whether `pattern` of a `credential.<url>` section applies to `url`, a `*` matches any
+/// one label of the host name.
+fn url_matches(pattern: &str, url: &Url) -> bool {
+    let pattern = match Url::parse(pattern) {
+        Ok(pattern) => pattern,
+        Err(_) => return false,
+    };
+
+    let hosts_match = match (pattern.host_str(), url.host_str()) {
+        (Some(pattern), Some(host)) => {
+            let pattern = pattern.split('.').collect::<Vec<_>>();
+            let host = host.split('.').collect::<Vec<_>>();
+
+            pattern.len() == host.len()
+                && pattern
+                    .iter()
+                    .zip(host)
+                    .all(|(pattern, label)| *pattern == "*" || pattern.eq_ignore_ascii_case(label))
+        }
+        _ => false,
+    };
+
+    let users_match = pattern.username().is_empty() || pattern.username() == url.username();
+
+    let path = pattern.path().trim_end_matches('/');
+    let paths_match = path.is_empty()
+        || url.path() == path
+        || url
+            .path()
+            .strip_prefix(path)
+            .map(|rest| rest.starts_with('/'))
+            .unwrap_or(false);
+
+    pattern.scheme() == url.scheme()
+        && hosts_match
+        && pattern.port() == url.port()
+        && users_match
+        && paths_match
+}
+
+/// The shell command git runs for `helper`: commands starting with `!` and absolute paths
+/// as they are, anything else is `git credential-<helper>`.
+fn helper_command(helper: &str, action: &str) -> String {
+    if let Some(command) = helper.strip_prefix('!') {
+        format!("{command} {action}")
+    } else if helper.starts_with('/') {
+        format!("{helper} {action}")
+    } else {
+        format!("git credential-{helper} {action}")
+    }
+}
+
+fn run_helper(helper: &str, action: &str, input: &str) -> Option<String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(helper_command(helper, action))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let mut stdin = child.stdin.take()?;
+    let _ = stdin.write_all(input.as_bytes());
+    let _ = stdin.write_all(b"\n");
+    drop(stdin);
+
+    let output = child.wait_with_output().ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_patterns() {
+        let url = Url::parse("https://git.company.com/mobile/ios.git").unwrap();
+
+        assert!(url_matches("https://git.company.com", &url));
+        assert!(url_matches("https://*.company.com/", &url));
+        assert!(url_matches("https://git.company.com/mobile", &url));
+        assert!(!url_matches("https://git.company.com/mob", &url));
+        assert!(!url_matches("http://git.company.com", &url));
+        assert!(!url_matches("https://*.com", &url));
+        assert!(!url_matches("https://git.company.com:8443", &url));
+        assert!(!url_matches("https://me@git.company.com", &url));
+    }
+
+    #[test]
+    fn helper_commands() {
+        assert_eq!(
+            helper_command("store --file ~/.git-credentials", "get"),
+            "git credential-store --file ~/.git-credentials get"
+        );
+        assert_eq!(
+            helper_command("/usr/lib/git-core/git-credential-libsecret", "erase"),
+            "/usr/lib/git-core/git-credential-libsecret erase"
+        );
+        assert_eq!(
+            helper_command("!f() { echo password=secret; }; f", "get"),
+            "f() { echo password=secret; }; f get"
+        );
+    }
+}
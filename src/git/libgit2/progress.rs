@@ -0,0 +1,18 @@
+mod formatter;
+mod reporter;
+
+use std::io::{self, Write};
+
+pub use reporter::{report_push, report_transfer, State};
+
+/// Passes on what the remote says while it prepares the pack, like `remote: Counting objects`.
+pub fn report_sideband(text: &[u8]) -> bool {
+    if let Ok(text) = std::str::from_utf8(text) {
+        // progress lines end with `\r` to be overwritten by the next one
+        for line in text.split_inclusive(['\r', '\n']) {
+            eprint!("remote: {line}");
+        }
+        io::stderr().flush().unwrap();
+    }
+    true
+}
@@ -24,7 +24,7 @@ impl State {
     }
 }
 
-pub fn report_clone(state: &mut State, raw: git2::Progress<'_>) {
+pub fn report_transfer(state: &mut State, raw: git2::Progress<'_>) {
     let is_finished =
         raw.received_objects() == raw.total_objects() && raw.indexed_deltas() == raw.total_deltas();
 
@@ -51,7 +51,7 @@ pub fn report_clone(state: &mut State, raw: git2::Progress<'_>) {
     let bytes = format_bytes_count(progress.bytes);
     let speed = format_bytes_rate_count(throughput.speed(&state.throughput));
 
-    let mut output = if state.fetch_done {
+    let output = if state.fetch_done {
         if progress.total_deltas == 0 {
             return;
         }
@@ -75,22 +75,67 @@ pub fn report_clone(state: &mut State, raw: git2::Progress<'_>) {
         format!("Receiving objects: {percent:3}% ({received}/{total}), {bytes} | {speed}{end}")
     };
 
-    let current_length = output.len();
+    state.print(output, progress, throughput);
+}
+
+/// Reports the objects sent by a push, like `report_transfer` does for the received ones.
+pub fn report_push(state: &mut State, current: usize, total: usize, bytes: usize) {
+    let is_finished = current == total;
+
+    let now = Instant::now();
+    if now.duration_since(state.progress.ts) < Duration::from_millis(100) && !is_finished {
+        return;
+    }
 
-    if current_length < state.last_length {
-        output.insert_str(
-            current_length - 1,
-            " ".repeat(state.last_length - current_length + 1).as_str(),
-        );
+    // libgit2 can report the last step more than once
+    if total == 0 || state.fetch_done {
+        return;
     }
 
-    eprint!("{output}");
+    let progress = Progress {
+        bytes,
+        received_objects: current,
+        total_objects: total,
+        ..Progress::new(now)
+    };
+
+    let throughput = Throughput {
+        bytes: progress.bytes.saturating_sub(state.progress.bytes),
+        micros: now.duration_since(state.progress.ts).as_micros(),
+    };
+
+    let percent = (100 * current) / total;
+    let bytes = format_bytes_count(bytes);
+    let speed = format_bytes_rate_count(throughput.speed(&state.throughput));
+
+    state.fetch_done = is_finished;
+    let end = if is_finished { ", done.\n" } else { "\r" };
+
+    let output =
+        format!("Writing objects: {percent:3}% ({current}/{total}), {bytes} | {speed}{end}");
+
+    state.print(output, progress, throughput);
+}
+
+impl State {
+    fn print(&mut self, mut output: String, progress: Progress, throughput: Throughput) {
+        let current_length = output.len();
+
+        if current_length < self.last_length {
+            output.insert_str(
+                current_length - 1,
+                " ".repeat(self.last_length - current_length + 1).as_str(),
+            );
+        }
+
+        eprint!("{output}");
 
-    state.progress = progress;
-    state.throughput = throughput;
-    state.last_length = current_length;
+        self.progress = progress;
+        self.throughput = throughput;
+        self.last_length = current_length;
 
-    io::stderr().flush().unwrap();
+        io::stderr().flush().unwrap();
+    }
 }
 
 #[derive(Default)]
@@ -101,7 +146,7 @@ struct Throughput {
 
 impl Throughput {
     pub fn speed(&self, other: &Throughput) -> usize {
-        (self.bytes + other.bytes) / (self.micros + other.micros) as usize * 1000000usize
+        (self.bytes + other.bytes) / (self.micros + other.micros).max(1) as usize * 1000000usize
     }
 }
 
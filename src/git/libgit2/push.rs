@@ -0,0 +1,121 @@
+use std::cell::{Cell, RefCell};
+
+use git2::{
+    BranchType, ErrorClass, ErrorCode, Oid, PushOptions, Remote, RemoteCallbacks, Repository,
+};
+
+use super::credential_helper::CredentialHelper;
+use super::progress::{report_push, report_sideband, State};
+use crate::git::AuthDomainConfig;
+use crate::Error;
+
+pub fn push_branch<Conf>(
+    remote: &mut Remote,
+    branch: &str,
+    set_upstream: bool,
+    force_with_lease: bool,
+    repo: &Repository,
+    config: &Conf,
+) -> Result<(), Error>
+where
+    Conf: AuthDomainConfig,
+{
+    let remote_name = remote.name().unwrap().to_string();
+    println!("pushing {branch} to {remote_name}");
+
+    // the lease is what the branch was on the remote as of the last fetch
+    let expected = repo
+        .refname_to_id(&format!("refs/remotes/{remote_name}/{branch}"))
+        .unwrap_or(Oid::zero());
+
+    let url = remote.url().unwrap_or_default().to_string();
+    let credential_helper = CredentialHelper::new(&url, repo.config().ok().as_ref());
+
+    // libgit2 connects to the host of the url as is, an ssh alias is reached through an
+    // anonymous remote
+    let mut alias = match credential_helper.connect_url() {
+        Some(connect_url) => Some(repo.remote_anonymous(connect_url)?),
+        None => None,
+    };
+    let credential_helper = RefCell::new(credential_helper);
+
+    let state = RefCell::new(State::new());
+    let stale = Cell::new(false);
+    let rejected = RefCell::new(None);
+
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|url, username_from_url, allowed_types| {
+        let mut credential_helper = credential_helper.borrow_mut();
+        credential_helper.credentials(url, username_from_url, allowed_types, config)
+    });
+    callbacks.push_transfer_progress(|current, total, bytes| {
+        let mut state = state.borrow_mut();
+        report_push(&mut state, current, total, bytes);
+    });
+    callbacks.sideband_progress(report_sideband);
+    callbacks.push_negotiation(|updates| {
+        if force_with_lease && updates.iter().any(|update| update.src() != expected) {
+            stale.set(true);
+            return Err(git2::Error::from_str("stale info"));
+        }
+        Ok(())
+    });
+    callbacks.push_update_reference(|_, status| {
+        if let Some(status) = status {
+            *rejected.borrow_mut() = Some(status.to_string());
+        }
+        Ok(())
+    });
+
+    let mut po = PushOptions::new();
+    po.remote_callbacks(callbacks);
+
+    let force = if force_with_lease { "+" } else { "" };
+    let refspec = format!("{force}refs/heads/{branch}:refs/heads/{branch}");
+
+    let pushed = match alias.as_mut() {
+        Some(alias) => alias.push(&[refspec], Some(&mut po)),
+        None => remote.push(&[refspec], Some(&mut po)),
+    };
+
+    match pushed {
+        Ok(()) => {}
+        Err(_) if stale.get() => return Err(Error::StaleLease(branch.to_string())),
+        Err(err)
+            if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFastForward =>
+        {
+            return Err(Error::PushRejected(
+                branch.to_string(),
+                "non-fast-forward".to_string(),
+            ))
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    // the remote accepted the credentials even if it rejected the update
+    credential_helper.borrow().approve();
+
+    if let Some(reason) = rejected.take() {
+        return Err(Error::PushRejected(branch.to_string(), reason));
+    }
+
+    if alias.is_some() {
+        // an anonymous remote has no fetch refspecs to update the remote-tracking branch by
+        let pushed = repo.refname_to_id(&format!("refs/heads/{branch}"))?;
+        repo.reference(
+            &format!("refs/remotes/{remote_name}/{branch}"),
+            pushed,
+            true,
+            "update by push",
+        )?;
+    }
+
+    if set_upstream {
+        let mut local_branch = repo.find_branch(branch, BranchType::Local)?;
+        local_branch.set_upstream(Some(&format!("{remote_name}/{branch}")))?;
+
+        println!("branch {branch} set up to track {remote_name}/{branch}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,554 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use url::Url;
+
+use crate::shellquote;
+
+/// Keys `ssh` tries when neither its command line nor its config name one.
+const DEFAULT_IDENTITIES: &[&str] = &["id_rsa", "id_ecdsa", "id_ed25519"];
+
+/// Deep enough for any sane config, shallow enough to stop `Include` cycles.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// How `ssh` would connect to a host, libgit2 knows nothing about ssh config.
+#[derive(Debug, PartialEq)]
+pub struct SshHost {
+    pub host_name: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_files: Vec<PathBuf>,
+}
+
+impl SshHost {
+    /// Resolves `host` the way `ssh` does: options of `ssh_command` first, then the
+    /// user's and the system's config, the first value of each option wins.
+    pub fn resolve(host: &str, ssh_command: Option<&str>) -> SshHost {
+        let home = dirs::home_dir().unwrap_or_default();
+        let mut options = SshOptions::default();
+
+        let config_file = ssh_command.and_then(|command| options.apply_command(command));
+
+        match config_file {
+            Some(path) => options.apply_file(&expand_tilde(&path, &home), host, 0),
+            None => {
+                options.apply_file(&home.join(".ssh").join("config"), host, 0);
+                options.apply_file(Path::new("/etc/ssh/ssh_config"), host, 0);
+            }
+        }
+
+        options.into_host(host, &home)
+    }
+}
+
+/// The parts of an ssh remote url that matter for connecting to it.
+#[derive(Debug, PartialEq)]
+pub struct SshUrl {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub path: String,
+}
+
+impl SshUrl {
+    /// Parses `ssh://[user@]host[:port]/path` and `[user@]host:path`, `None` for urls of
+    /// other transports.
+    pub fn parse(url: &str) -> Option<SshUrl> {
+        if let Some((scheme, _)) = url.split_once("://") {
+            if !matches!(scheme, "ssh" | "git+ssh" | "ssh+git") {
+                return None;
+            }
+
+            let url = Url::parse(url).ok()?;
+            let user = Some(url.username())
+                .filter(|user| !user.is_empty())
+                .map(String::from);
+
+            return Some(SshUrl {
+                user,
+                host: url.host_str()?.to_string(),
+                port: url.port(),
+                path: url.path().to_string(),
+            });
+        }
+
+        // scp-like syntax, a colon before the first slash
+        let (authority, path) = url.split_once(':')?;
+        if authority.contains('/') || authority.is_empty() {
+            return None;
+        }
+
+        let (user, host) = match authority.rsplit_once('@') {
+            Some((user, host)) => (Some(user.to_string()), host),
+            None => (None, authority),
+        };
+
+        Some(SshUrl {
+            user,
+            host: host.to_string(),
+            port: None,
+            path: path.to_string(),
+        })
+    }
+
+    /// The url to connect to `ssh_host` instead, when it isn't the host of this one.
+    pub fn connect_url(&self, ssh_host: &SshHost) -> Option<String> {
+        let port = self.port.or(ssh_host.port);
+
+        if ssh_host.host_name == self.host && port == self.port {
+            return None;
+        }
+
+        let user = match &self.user {
+            Some(user) => format!("{user}@"),
+            None => String::new(),
+        };
+        let port = match port {
+            Some(port) => format!(":{port}"),
+            None => String::new(),
+        };
+
+        // scp-like paths are relative to the home of the user on the server, for git
+        // hosting services that is the same as the root
+        let separator = if self.path.starts_with('/') { "" } else { "/" };
+
+        Some(format!(
+            "ssh://{user}{host}{port}{separator}{path}",
+            host = ssh_host.host_name,
+            path = self.path
+        ))
+    }
+}
+
+/// The command to run ssh with, `GIT_SSH_COMMAND` first and then `core.sshCommand`.
+pub fn ssh_command(git_config: Option<&git2::Config>) -> Option<String> {
+    std::env::var("GIT_SSH_COMMAND")
+        .ok()
+        .filter(|command| !command.trim().is_empty())
+        .or_else(|| git_config?.get_string("core.sshCommand").ok())
+}
+
+#[derive(Debug, Default)]
+struct SshOptions {
+    host_name: Option<String>,
+    user: Option<String>,
+    port: Option<u16>,
+    identity_files: Vec<String>,
+}
+
+impl SshOptions {
+    /// Applies the options of an ssh command line, returns the config file passed with `-F`.
+    fn apply_command(&mut self, command: &str) -> Option<String> {
+        let words = shellquote::split(command)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_default();
+
+        let mut config_file = None;
+
+        // the first word is the program
+        let mut words = words.into_iter().skip(1);
+
+        while let Some(word) = words.next() {
+            let flag = match word.strip_prefix('-') {
+                Some(flags) if !flags.is_empty() => flags,
+                // the host and the command to run on it
+                _ => break,
+            };
+
+            // flags may be grouped like `-vA` and take their argument joined like `-p22`
+            for (index, letter) in flag.char_indices() {
+                if !takes_argument(letter) {
+                    continue;
+                }
+
+                let joined = &flag[index + letter.len_utf8()..];
+                let argument = if joined.is_empty() {
+                    words.next().unwrap_or_default()
+                } else {
+                    joined.to_string()
+                };
+
+                match letter {
+                    'i' => self.identity_files.push(argument),
+                    'p' => self.apply("Port", &argument),
+                    'l' => self.apply("User", &argument),
+                    'F' => config_file = config_file.or(Some(argument)),
+                    'o' => {
+                        if let Some((key, value)) = split_option(&argument) {
+                            self.apply(key, &value);
+                        }
+                    }
+                    _ => {}
+                }
+
+                break;
+            }
+        }
+
+        config_file
+    }
+
+    fn apply_file(&mut self, path: &Path, host: &str, depth: usize) {
+        if let Ok(contents) = fs::read_to_string(path) {
+            self.apply_config(&contents, host, depth);
+        }
+    }
+
+    fn apply_config(&mut self, contents: &str, host: &str, depth: usize) {
+        // options before the first `Host` apply to all hosts
+        let mut matching = true;
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = match split_option(line) {
+                Some(option) => option,
+                None => continue,
+            };
+
+            if key.eq_ignore_ascii_case("Host") {
+                matching = host_matches(&value, host);
+            } else if key.eq_ignore_ascii_case("Match") {
+                // criteria like `exec` can't be evaluated here
+                matching = false;
+            } else if !matching {
+                continue;
+            } else if key.eq_ignore_ascii_case("Include") {
+                if depth < MAX_INCLUDE_DEPTH {
+                    for path in include_paths(&value) {
+                        self.apply_file(&path, host, depth + 1);
+                    }
+                }
+            } else {
+                self.apply(key, &value);
+            }
+        }
+    }
+
+    fn apply(&mut self, key: &str, value: &str) {
+        if key.eq_ignore_ascii_case("HostName") {
+            self.host_name = self.host_name.take().or(Some(value.to_string()));
+        } else if key.eq_ignore_ascii_case("User") {
+            self.user = self.user.take().or(Some(value.to_string()));
+        } else if key.eq_ignore_ascii_case("Port") {
+            self.port = self.port.or(value.parse().ok());
+        } else if key.eq_ignore_ascii_case("IdentityFile") {
+            self.identity_files.push(value.to_string());
+        }
+    }
+
+    fn into_host(self, host: &str, home: &Path) -> SshHost {
+        let host_name = match self.host_name {
+            Some(host_name) => host_name.replace("%h", host),
+            None => host.to_string(),
+        };
+
+        let identity_files = if self.identity_files.is_empty() {
+            DEFAULT_IDENTITIES
+                .iter()
+                .map(|name| home.join(".ssh").join(name))
+                .collect()
+        } else {
+            let user = self.user.as_deref().unwrap_or("git");
+
+            self.identity_files
+                .iter()
+                .filter(|path| !path.eq_ignore_ascii_case("none"))
+                .map(|path| {
+                    let path = expand_tokens(path, home, &host_name, user);
+                    expand_tilde(&path, home)
+                })
+                .collect()
+        };
+
+        SshHost {
+            host_name,
+            user: self.user,
+            port: self.port,
+            identity_files,
+        }
+    }
+}
+
+/// Splits `Key value`, `Key=value` and `Key = "value"` into the key and the unquoted value.
+fn split_option(line: &str) -> Option<(&str, String)> {
+    let line = line.trim();
+    let end = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (key, rest) = line.split_at(end);
+
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest).trim();
+
+    let value = shellquote::split(rest)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?
+        .join(" ");
+
+    Some((key, value))
+}
+
+fn takes_argument(flag: char) -> bool {
+    "BbcDEeFIiJLlmOoPpQRSWw".contains(flag)
+}
+
+/// Whether `host` matches one of the space separated `patterns` and none of the negated ones.
+fn host_matches(patterns: &str, host: &str) -> bool {
+    let mut matches = false;
+
+    for pattern in patterns.split_whitespace() {
+        match pattern.strip_prefix('!') {
+            Some(negated) if wildcard_match(negated, host) => return false,
+            Some(_) => {}
+            None => matches |= wildcard_match(pattern, host),
+        }
+    }
+
+    matches
+}
+
+/// Matches `*` and `?` wildcards, case insensitive like host names are.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let value = value.to_lowercase().chars().collect::<Vec<_>>();
+
+    let (mut p, mut v) = (0, 0);
+    let mut star = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, v));
+            p += 1;
+        } else if let Some((star_p, star_v)) = star {
+            p = star_p + 1;
+            v = star_v + 1;
+            star = Some((star_p, star_v + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Paths of an `Include`, relative ones are in `~/.ssh` and file names may have wildcards.
+fn include_paths(value: &str) -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+
+    value
+        .split_whitespace()
+        .flat_map(|pattern| {
+            let path = expand_tilde(pattern, &home);
+            let path = if path.is_relative() {
+                home.join(".ssh").join(path)
+            } else {
+                path
+            };
+
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if !name.contains(['*', '?']) {
+                return vec![path];
+            }
+
+            let dir = path.parent().unwrap_or(Path::new("/"));
+            let mut paths = fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .map(|file| wildcard_match(&name, &file.to_string_lossy()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>();
+
+            paths.sort();
+            paths
+        })
+        .collect()
+}
+
+/// Expands the `%d`, `%h`, `%r` and `%%` tokens of an `IdentityFile`.
+fn expand_tokens(value: &str, home: &Path, host_name: &str, user: &str) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            expanded.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('d') => expanded.push_str(&home.to_string_lossy()),
+            Some('h') => expanded.push_str(host_name),
+            Some('r') => expanded.push_str(user),
+            Some('%') => expanded.push('%'),
+            Some(other) => {
+                expanded.push('%');
+                expanded.push(other);
+            }
+            None => expanded.push('%'),
+        }
+    }
+
+    expanded
+}
+
+fn expand_tilde(value: &str, home: &Path) -> PathBuf {
+    match value.strip_prefix("~/") {
+        Some(relative) => home.join(relative),
+        None if value == "~" => home.to_path_buf(),
+        None => PathBuf::from(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+IdentityFile ~/.ssh/id_global
+
+Host work-gitlab
+    HostName gitlab.company.com
+    Port 2222
+    IdentityFile %d/.ssh/work_%h
+
+Host *.company.com !bitbucket.company.com
+    User = deploy
+    IdentityFile "~/.ssh/company key"
+
+Match exec "true"
+    User nobody
+
+Host *
+    User git
+    Port 22
+"#;
+
+    fn resolve(host: &str, command: Option<&str>) -> SshHost {
+        let mut options = SshOptions::default();
+        if let Some(command) = command {
+            options.apply_command(command);
+        }
+        options.apply_config(CONFIG, host, 0);
+        options.into_host(host, Path::new("/home/me"))
+    }
+
+    #[test]
+    fn host_blocks() {
+        assert_eq!(
+            resolve("work-gitlab", None),
+            SshHost {
+                host_name: "gitlab.company.com".to_string(),
+                user: Some("git".to_string()),
+                port: Some(2222),
+                identity_files: vec![
+                    PathBuf::from("/home/me/.ssh/id_global"),
+                    PathBuf::from("/home/me/.ssh/work_gitlab.company.com"),
+                ],
+            }
+        );
+
+        let bitbucket = resolve("bitbucket.company.com", None);
+        assert_eq!(bitbucket.user.as_deref(), Some("git"));
+        assert_eq!(bitbucket.identity_files.len(), 1);
+
+        let jira = resolve("JIRA.company.com", None);
+        assert_eq!(jira.host_name, "JIRA.company.com");
+        assert_eq!(jira.user.as_deref(), Some("deploy"));
+        assert_eq!(jira.port, Some(22));
+        assert_eq!(
+            jira.identity_files[1],
+            PathBuf::from("/home/me/.ssh/company key")
+        );
+    }
+
+    #[test]
+    fn default_identities() {
+        let mut options = SshOptions::default();
+        options.apply_config("Host github.com\n  User git\n", "github.com", 0);
+        let host = options.into_host("github.com", Path::new("/home/me"));
+
+        assert_eq!(
+            host.identity_files,
+            vec![
+                PathBuf::from("/home/me/.ssh/id_rsa"),
+                PathBuf::from("/home/me/.ssh/id_ecdsa"),
+                PathBuf::from("/home/me/.ssh/id_ed25519"),
+            ]
+        );
+    }
+
+    #[test]
+    fn command_overrides() {
+        let host = resolve(
+            "work-gitlab",
+            Some("ssh -v -i ~/.ssh/ci_key -p2200 -o 'User=ci' -o HostName=10.0.0.1"),
+        );
+
+        assert_eq!(host.host_name, "10.0.0.1");
+        assert_eq!(host.user.as_deref(), Some("ci"));
+        assert_eq!(host.port, Some(2200));
+        assert_eq!(
+            host.identity_files[0],
+            PathBuf::from("/home/me/.ssh/ci_key")
+        );
+
+        let mut options = SshOptions::default();
+        assert_eq!(
+            options.apply_command("ssh -F /tmp/ssh_config -l me"),
+            Some("/tmp/ssh_config".to_string())
+        );
+        assert_eq!(options.user.as_deref(), Some("me"));
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.company.com", "git.company.com"));
+        assert!(wildcard_match("git?ub.com", "GitHub.com"));
+        assert!(!wildcard_match("*.company.com", "company.com"));
+        assert!(host_matches("a b *.c", "x.c"));
+        assert!(!host_matches("* !x.c", "x.c"));
+    }
+
+    #[test]
+    fn urls() {
+        let scp = SshUrl::parse("git@work-gitlab:ios/app.git").unwrap();
+        assert_eq!(scp.user.as_deref(), Some("git"));
+        assert_eq!(scp.host, "work-gitlab");
+        assert_eq!(scp.path, "ios/app.git");
+
+        let ssh = SshUrl::parse("ssh://git@bitbucket.company.com:7999/ap/ios.git").unwrap();
+        assert_eq!(ssh.port, Some(7999));
+        assert_eq!(ssh.path, "/ap/ios.git");
+
+        assert_eq!(SshUrl::parse("https://github.com/chipp/gitext.git"), None);
+        assert_eq!(SshUrl::parse("/tmp/repo"), None);
+
+        let work_gitlab = resolve("work-gitlab", None);
+        assert_eq!(
+            scp.connect_url(&work_gitlab).as_deref(),
+            Some("ssh://git@gitlab.company.com:2222/ios/app.git")
+        );
+
+        let same = SshHost {
+            host_name: "bitbucket.company.com".to_string(),
+            user: None,
+            port: Some(22),
+            identity_files: vec![],
+        };
+        assert_eq!(ssh.connect_url(&same), None);
+    }
+}
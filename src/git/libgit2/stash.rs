@@ -0,0 +1,21 @@
+use git2::{Repository, StashFlags};
+
+use crate::Error;
+
+// stashing needs a mutable repository, the rest of gitext shares an immutable one
+
+pub fn stash_changes(message: &str, repo: &Repository) -> Result<(), Error> {
+    let mut repo = Repository::open(repo.path())?;
+    let signature = repo.signature()?;
+
+    repo.stash_save(&signature, message, Some(StashFlags::INCLUDE_UNTRACKED))?;
+
+    Ok(())
+}
+
+pub fn apply_stash(index: usize, repo: &Repository) -> Result<(), Error> {
+    let mut repo = Repository::open(repo.path())?;
+    repo.stash_pop(index, None)?;
+
+    Ok(())
+}
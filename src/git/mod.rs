@@ -1,14 +1,27 @@
+mod autostash;
 mod config;
+mod divergence;
+mod hooks;
+mod sync;
+mod ticket;
+mod worktree;
 
 #[cfg(feature = "git-cli")]
 mod git_cli {
     pub mod branch;
     pub mod clone;
     pub mod fetch;
+    pub mod push;
+    pub mod stash;
 }
 
 #[cfg(feature = "git-cli")]
 pub use git_cli::branch::switch_to_branch;
+#[cfg(all(test, feature = "git-cli"))]
+pub use git_cli::branch::switch_to_branch_with;
+
+#[cfg(feature = "git-cli")]
+use git_cli::stash::{apply_stash, stash_changes};
 
 #[cfg(feature = "git-cli")]
 pub use git_cli::clone::clone_repo;
@@ -16,17 +29,30 @@ pub use git_cli::clone::clone_repo;
 #[cfg(feature = "git-cli")]
 pub use git_cli::fetch::fetch_remote;
 
+#[cfg(feature = "git-cli")]
+pub use git_cli::push::push_branch;
+
 #[cfg(not(feature = "git-cli"))]
 mod libgit2 {
     pub mod branch;
     pub mod clone;
     pub mod fetch;
+    pub mod push;
+    pub mod stash;
 
     mod credential_helper;
+    mod git_credential;
+    mod progress;
+    mod ssh;
 }
 
 #[cfg(not(feature = "git-cli"))]
 pub use libgit2::branch::switch_to_branch;
+#[cfg(all(test, not(feature = "git-cli")))]
+pub use libgit2::branch::switch_to_branch_with;
+
+#[cfg(not(feature = "git-cli"))]
+use libgit2::stash::{apply_stash, stash_changes};
 
 #[cfg(not(feature = "git-cli"))]
 pub use libgit2::clone::clone_repo;
@@ -34,18 +60,39 @@ pub use libgit2::clone::clone_repo;
 #[cfg(not(feature = "git-cli"))]
 pub use libgit2::fetch::fetch_remote;
 
+#[cfg(not(feature = "git-cli"))]
+pub use libgit2::push::push_branch;
+
 use std::ffi::OsStr;
 use std::path::Path;
 use std::process::{exit, Command};
 
+pub use autostash::switch_with_autostash;
+pub use config::{
+    get_aliases_from_config, get_config, get_configured_hosts, get_hooks_config, get_host_config,
+    get_prs_columns_from_config, get_workspace_from_config, set_config, set_host_config,
+    set_provider, Config, ConfigError, JiraAuth, JiraPrLink, Provider, Tracker,
+};
 pub use config::{
-    get_aliases_from_config, get_config, set_config, set_provider, Config, ConfigError, Provider,
+    AuthDomainConfig, AutostashConfig, BaseUrlConfig, CommitHooksConfig, JiraLinkConfig,
+    JiraTransitionsConfig, JiraUrlConfig, PrsColumnsConfig, RequiredApprovalsConfig,
+    TicketPatternConfig, TicketStartConfig, TicketsJqlConfig, TrackerConfig, WorktreeConfig,
+};
+pub use divergence::{backup_branch_name, BranchState};
+pub use hooks::{
+    chained_hook_path, get_hooks_dir, hook_script, is_gitext_hook, is_only_prefix, message_subject,
+    prefix_message, HOOKS,
+};
+pub use sync::sync_branch;
+pub use ticket::{
+    TicketPattern, DEFAULT_ISSUE_PATTERN, DEFAULT_TICKET_PATTERN, LENIENT_TICKET_PATTERN,
+};
+pub use worktree::{
+    checkout_pr_worktree, pr_worktrees, remove_pr_worktree, worktree_path, PrWorktree,
 };
-pub use config::{AuthDomainConfig, BaseUrlConfig, JiraUrlConfig};
 
-use git2::{Branch, BranchType, Remote, RepositoryOpenFlags};
+use git2::{Branch, BranchType, Remote, RepositoryOpenFlags, StatusOptions};
 use git2::{Error as GitError, Repository};
-use regex::Regex;
 
 use super::Error;
 
@@ -69,6 +116,19 @@ pub fn get_current_branch(repo: &Repository) -> Option<String> {
     }
 }
 
+/// Whether the work tree has changes, untracked files included.
+pub fn has_uncommitted_changes(repo: &Repository) -> Result<bool, GitError> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+
+    Ok(!repo.statuses(Some(&mut options))?.is_empty())
+}
+
+pub fn get_head_commit_summary(repo: &Repository) -> Option<String> {
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    commit.summary().map(String::from)
+}
+
 pub fn get_current_branch_upstream_remote(repo: &Repository) -> Option<String> {
     let branch = get_current_branch(repo)?;
     let refname = format!("refs/heads/{branch}");
@@ -99,6 +159,49 @@ where
     })
 }
 
+/// Branch the remote's `HEAD` points to, as of the last fetch.
+pub fn get_remote_default_branch(remote: &Remote, repo: &Repository) -> Option<String> {
+    let remote_name = remote.name()?;
+    let head = repo
+        .find_reference(&format!("refs/remotes/{remote_name}/HEAD"))
+        .ok()?;
+
+    head.symbolic_target()?
+        .strip_prefix(&format!("refs/remotes/{remote_name}/"))
+        .map(String::from)
+}
+
+/// What `fetch_remote` fetches.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FetchSpec<'a> {
+    /// The only branch to fetch, everything the remote's refspecs cover when `None`.
+    pub branch: Option<&'a str>,
+    /// Whether remote-tracking branches deleted from the remote are removed, `fetch.prune`
+    /// decides when it's not set.
+    pub prune: bool,
+}
+
+impl<'a> FetchSpec<'a> {
+    pub fn branch(branch: &'a str, prune: bool) -> Self {
+        FetchSpec {
+            branch: Some(branch),
+            prune,
+        }
+    }
+
+    /// Refspecs to fetch instead of the remote's ones, empty when those are fine.
+    pub fn refspecs(&self, remote_name: &str) -> Vec<String> {
+        match self.branch {
+            // pruning only looks at the refs that are fetched
+            Some(_) if self.prune => vec![],
+            Some(branch) => vec![format!(
+                "+refs/heads/{branch}:refs/remotes/{remote_name}/{branch}"
+            )],
+            None => vec![],
+        }
+    }
+}
+
 pub fn find_remote_branch<'repo>(
     branch_name: &str,
     remote: &Remote,
@@ -114,13 +217,78 @@ pub fn find_remote_branch<'repo>(
     Ok(branch)
 }
 
-pub fn extract_ticket<'b>(branch: &'b str) -> Option<&'b str> {
-    let re = Regex::new(r"\w{2,}-\d+").unwrap();
-    re.captures(&branch)
-        .map(|caps| caps.get(0).unwrap().as_str())
+/// Subjects of the commits on `HEAD` that aren't on any remote's default branch, newest first.
+pub fn get_branch_commit_subjects(repo: &Repository) -> Vec<String> {
+    const MAX_COMMITS: usize = 100;
+
+    let mut walk = match repo.revwalk() {
+        Ok(walk) => walk,
+        Err(_) => return vec![],
+    };
+
+    if walk.push_head().is_err() {
+        return vec![];
+    }
+
+    let mut has_base = false;
+
+    if let Ok(remotes) = repo.remotes() {
+        for remote_name in remotes.iter().flatten() {
+            let base = repo
+                .find_reference(&format!("refs/remotes/{remote_name}/HEAD"))
+                .and_then(|reference| reference.peel_to_commit());
+
+            if let Ok(base) = base {
+                has_base |= walk.hide(base.id()).is_ok();
+            }
+        }
+    }
+
+    // without a base every commit in the history would count as the branch's
+    if !has_base {
+        return vec![];
+    }
+
+    walk.flatten()
+        .take(MAX_COMMITS)
+        .filter_map(|oid| Some(repo.find_commit(oid).ok()?.summary()?.to_string()))
+        .collect()
+}
+
+/// Fills `{key}` and `{slug}` in a branch name template like `feature/{key}-{slug}`.
+pub fn branch_name_for_ticket(template: &str, key: &str, summary: &str) -> String {
+    const MAX_SLUG_LENGTH: usize = 50;
+
+    let mut slug = String::new();
+
+    for word in summary
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        // words with letters branch names can't have are dropped rather than mangled
+        .filter(|word| !word.is_empty() && word.is_ascii())
+    {
+        if !slug.is_empty() && slug.len() + word.len() + 1 > MAX_SLUG_LENGTH {
+            break;
+        }
+
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+
+        slug.push_str(word);
+    }
+
+    slug.truncate(MAX_SLUG_LENGTH);
+
+    template
+        .replace("{key}", key)
+        .replace("{slug}", &slug)
+        .trim_end_matches(['-', '/'])
+        .to_string()
 }
 
-pub fn exec_git_cmd<A, I>(args: I, repo: Option<&Repository>) -> Result<(), Error>
+/// `git` with `args`, run in `repo` when it's set.
+pub fn git_command<A, I>(args: I, repo: Option<&Repository>) -> Command
 where
     A: AsRef<OsStr>,
     I: IntoIterator<Item = A>,
@@ -135,7 +303,16 @@ where
         }
     }
 
-    let git = git.args(args);
+    git.args(args);
+    git
+}
+
+pub fn exec_git_cmd<A, I>(args: I, repo: Option<&Repository>) -> Result<(), Error>
+where
+    A: AsRef<OsStr>,
+    I: IntoIterator<Item = A>,
+{
+    let mut git = git_command(args, repo);
     // eprintln!(
     //     "\x1b[35mgit {}\x1b[0m",
     //     git.get_args()
@@ -158,18 +335,19 @@ where
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use std::fs;
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    struct TempRepo {
+    /// A repository in a temporary directory with `work` checked out, removed when dropped.
+    pub(crate) struct TempRepo {
         path: std::path::PathBuf,
-        repo: Repository,
+        pub(crate) repo: Repository,
     }
 
     impl TempRepo {
-        fn new() -> Self {
+        pub(crate) fn new() -> Self {
             let id = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
@@ -189,6 +367,22 @@ mod tests {
             Self { path, repo }
         }
 
+        /// A commit with the tree of the index, not on any branch.
+        pub(crate) fn commit(&self, message: &str, parents: &[git2::Oid]) -> git2::Oid {
+            let signature = git2::Signature::now("gitext", "gitext@example.com").unwrap();
+            let tree_id = self.repo.index().unwrap().write_tree().unwrap();
+            let tree = self.repo.find_tree(tree_id).unwrap();
+            let parents = parents
+                .iter()
+                .map(|id| self.repo.find_commit(*id).unwrap())
+                .collect::<Vec<_>>();
+            let parents = parents.iter().collect::<Vec<_>>();
+
+            self.repo
+                .commit(None, &signature, &signature, message, &tree, &parents)
+                .unwrap()
+        }
+
         fn set_upstream_remote(&self, remote: &str) {
             let mut config = self.repo.config().unwrap();
             config.set_str("branch.work.remote", remote).unwrap();
@@ -273,4 +467,44 @@ mod tests {
 
         assert_eq!(selected_remote_name(&temp.repo), Some("fork".to_string()));
     }
+
+    #[test]
+    fn branch_name_from_ticket() {
+        let template = "feature/{key}-{slug}";
+
+        let branch = branch_name_for_ticket(template, "IOS-1234", "Fix crash on launch (iOS 17)");
+        assert_eq!(branch, "feature/IOS-1234-fix-crash-on-launch-ios-17");
+        assert_eq!(
+            TicketPattern::default().find(&branch).as_deref(),
+            Some("IOS-1234")
+        );
+
+        let branch = branch_name_for_ticket(template, "IOS-1", "Ünïcode — only");
+        assert_eq!(branch, "feature/IOS-1-only");
+
+        let branch = branch_name_for_ticket(template, "IOS-1", "Café crème");
+        assert_eq!(branch, "feature/IOS-1");
+
+        let branch = branch_name_for_ticket(template, "IOS-1", "!!!");
+        assert_eq!(branch, "feature/IOS-1");
+
+        let summary = "a very long summary that goes on and on and on and never seems to end";
+        let branch = branch_name_for_ticket("{key}/{slug}", "IOS-1", summary);
+        assert_eq!(
+            branch,
+            "IOS-1/a-very-long-summary-that-goes-on-and-on-and-on-and"
+        );
+    }
+
+    #[test]
+    fn fetch_refspecs() {
+        assert!(FetchSpec::default().refspecs("origin").is_empty());
+        assert_eq!(
+            FetchSpec::branch("feature/IOS-1", false).refspecs("origin"),
+            ["+refs/heads/feature/IOS-1:refs/remotes/origin/feature/IOS-1"]
+        );
+        assert!(FetchSpec::branch("feature/IOS-1", true)
+            .refspecs("origin")
+            .is_empty());
+    }
 }
@@ -0,0 +1,44 @@
+use git2::{BranchType, Remote, Repository};
+
+use super::{push_branch, AuthDomainConfig, BranchState};
+use crate::Error;
+
+/// Pushes `branch` when the remote lacks some of its commits and sets its upstream when it
+/// has none. A rewritten branch is only pushed with `force`, with a lease on the remote branch.
+pub fn sync_branch<Conf>(
+    remote: &mut Remote,
+    branch: &str,
+    force: bool,
+    repo: &Repository,
+    config: &Conf,
+) -> Result<(), Error>
+where
+    Conf: AuthDomainConfig,
+{
+    let remote_name = remote.name().unwrap().to_string();
+
+    let local_branch = repo.find_branch(branch, BranchType::Local)?;
+    let local = local_branch.get().peel_to_commit()?.id();
+    let set_upstream = local_branch.upstream().is_err();
+
+    // as of the last fetch, that's what the lease is about too
+    let remote_tip = repo
+        .refname_to_id(&format!("refs/remotes/{remote_name}/{branch}"))
+        .ok();
+
+    let force_with_lease = match remote_tip {
+        None => false,
+        Some(remote_tip) => match BranchState::between(local, remote_tip, repo)? {
+            BranchState::UpToDate if !set_upstream => {
+                println!("{branch} is up to date with {remote_name}/{branch}");
+                return Ok(());
+            }
+            BranchState::UpToDate | BranchState::Ahead(_) => false,
+            BranchState::Behind(_) => return Err(Error::BranchBehind(branch.to_string())),
+            BranchState::Diverged { .. } if force => true,
+            BranchState::Diverged { .. } => return Err(Error::BranchDiverged(branch.to_string())),
+        },
+    };
+
+    push_branch(remote, branch, set_upstream, force_with_lease, repo, config)
+}
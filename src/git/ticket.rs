@@ -0,0 +1,219 @@
+use git2::Repository;
+use regex::Regex;
+
+/// Matches Jira keys like `IOS-1234`, `feature/IOS-1234` or `fix_IOS-12` but not `v1-2`
+/// or `release-2`.
+pub const DEFAULT_TICKET_PATTERN: &str = r"(?:^|[^A-Za-z0-9])([A-Z]{2}[A-Z0-9]*-\d+)";
+
+/// Also matches lowercase keys like `feature/ios-1234`. It's the default only with an
+/// allowlist of projects, without one `utf-8` or `swift-5` would be keys too.
+pub const LENIENT_TICKET_PATTERN: &str = r"(?i)(?:^|[^a-z0-9])([a-z]{2}[a-z0-9]*-\d+)";
+
+/// Matches issue numbers in branches like `123-fix-crash` or `fix/123` and in titles like `(#123)`.
+pub const DEFAULT_ISSUE_PATTERN: &str = r"(?:^|/)(\d+)(?:[-_]|$)|#(\d+)\b";
+
+/// Finds ticket keys in branch names, PR titles and commit subjects.
+///
+/// When the pattern has capture groups, the first one that matched is the key. Keys are
+/// uppercase, the way Jira has them.
+#[derive(Debug)]
+pub struct TicketPattern {
+    regex: Regex,
+    projects: Vec<String>,
+}
+
+impl Default for TicketPattern {
+    fn default() -> Self {
+        Self::new(DEFAULT_TICKET_PATTERN, vec![]).unwrap()
+    }
+}
+
+impl TicketPattern {
+    /// `projects` limits keys to these Jira projects, an empty list allows any project.
+    pub fn new(pattern: &str, projects: Vec<String>) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: Regex::new(pattern)?,
+            projects,
+        })
+    }
+
+    /// The first allowed key in `text`.
+    pub fn find(&self, text: &str) -> Option<String> {
+        self.keys(text)
+            .into_iter()
+            .find(|key| self.is_allowed(key))
+            .map(str::to_uppercase)
+    }
+
+    /// Projects of the allowlist, empty when any project is allowed.
+    pub fn projects(&self) -> &[String] {
+        &self.projects
+    }
+
+    /// Whether any key in `text` is `key`.
+    pub fn references(&self, text: &str, key: &str) -> bool {
+        self.keys(text)
+            .iter()
+            .any(|found| found.eq_ignore_ascii_case(key))
+    }
+
+    /// The first key found in `sources`, tried in order.
+    pub fn find_in(&self, sources: &[&str]) -> Option<String> {
+        sources.iter().find_map(|source| self.find(source))
+    }
+
+    /// The first key in the subjects of the commits made on the current branch.
+    pub fn find_in_commits(&self, repo: &Repository) -> Option<String> {
+        super::get_branch_commit_subjects(repo)
+            .iter()
+            .find_map(|subject| self.find(subject))
+    }
+
+    /// The key of a branch: in its name, then in its PR's `title`, then in the subjects
+    /// of its commits when `repo` has it checked out.
+    pub fn find_for_branch(
+        &self,
+        branch: &str,
+        title: Option<&str>,
+        repo: Option<&Repository>,
+    ) -> Option<String> {
+        self.find_in(&[branch, title.unwrap_or_default()])
+            .or_else(|| self.find_in_commits(repo?))
+    }
+
+    fn keys<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|caps| {
+                let key = match caps.len() {
+                    1 => caps.get(0),
+                    _ => caps.iter().skip(1).flatten().next(),
+                };
+
+                key.map(|key| key.as_str())
+            })
+            .collect()
+    }
+
+    fn is_allowed(&self, key: &str) -> bool {
+        if self.projects.is_empty() {
+            return true;
+        }
+
+        match key.rsplit_once('-') {
+            Some((project, _)) => self
+                .projects
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(project)),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_pattern() {
+        let pattern = TicketPattern::default();
+
+        assert_eq!(
+            pattern.find("feature/IOS-1234-fix-crash").as_deref(),
+            Some("IOS-1234")
+        );
+        assert_eq!(pattern.find("IOS-1234_fix").as_deref(), Some("IOS-1234"));
+        assert_eq!(pattern.find("release-2"), None);
+        assert_eq!(pattern.find("release-2.3"), None);
+        assert_eq!(pattern.find("v1-2"), None);
+        assert_eq!(pattern.find("fix/v1-2-IOS-7").as_deref(), Some("IOS-7"));
+        assert_eq!(pattern.find("fix_IOS-12").as_deref(), Some("IOS-12"));
+    }
+
+    #[test]
+    fn lowercase_words_are_not_keys() {
+        let pattern = TicketPattern::default();
+
+        assert_eq!(pattern.find("fix/utf-8-names"), None);
+        assert_eq!(pattern.find("feature/swift-5"), None);
+        assert_eq!(pattern.find("hotfix-3"), None);
+        assert_eq!(pattern.find("feature/ios-1234"), None);
+    }
+
+    #[test]
+    fn lowercase_keys_of_allowed_projects() {
+        let projects = vec!["IOS".to_string(), "AND2".to_string()];
+        let pattern = TicketPattern::new(LENIENT_TICKET_PATTERN, projects).unwrap();
+
+        assert_eq!(
+            pattern.find("feature/ios-1234").as_deref(),
+            Some("IOS-1234")
+        );
+        assert_eq!(pattern.find("fix_IOS-12").as_deref(), Some("IOS-12"));
+        assert_eq!(pattern.find("fix_and2-7-crash").as_deref(), Some("AND2-7"));
+        assert_eq!(pattern.find("fix/utf-8-ios-3").as_deref(), Some("IOS-3"));
+        assert_eq!(pattern.find("feature/swift-5"), None);
+        assert_eq!(pattern.find("release-2"), None);
+        assert!(pattern.references("ios-1234: Fix crash", "IOS-1234"));
+    }
+
+    #[test]
+    fn custom_pattern() {
+        let pattern = TicketPattern::new(r"(?i)\b([a-z]+-\d+)", vec![]).unwrap();
+        assert_eq!(
+            pattern.find("feature/ios-1234").as_deref(),
+            Some("IOS-1234")
+        );
+
+        let pattern = TicketPattern::new(r"#(\d+)", vec![]).unwrap();
+        assert_eq!(pattern.find("Fix crash (#42)").as_deref(), Some("42"));
+
+        assert!(TicketPattern::new(r"(", vec![]).is_err());
+    }
+
+    #[test]
+    fn issue_pattern() {
+        let pattern = TicketPattern::new(DEFAULT_ISSUE_PATTERN, vec![]).unwrap();
+
+        assert_eq!(pattern.find("123-fix-crash").as_deref(), Some("123"));
+        assert_eq!(pattern.find("fix/123").as_deref(), Some("123"));
+        assert_eq!(pattern.find("Fix crash (#123)").as_deref(), Some("123"));
+        assert_eq!(pattern.find("release-2"), None);
+        assert_eq!(pattern.find("v1.2.3"), None);
+    }
+
+    #[test]
+    fn allowed_projects() {
+        let pattern = TicketPattern::new(
+            DEFAULT_TICKET_PATTERN,
+            vec!["IOS".to_string(), "and".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(pattern.find("UTF-8 for IOS-1").as_deref(), Some("IOS-1"));
+        assert_eq!(pattern.find("AND-2").as_deref(), Some("AND-2"));
+        assert_eq!(pattern.find("WEB-3"), None);
+    }
+
+    #[test]
+    fn sources_order() {
+        let pattern = TicketPattern::default();
+
+        assert_eq!(
+            pattern
+                .find_in(&["feature/fix-crash", "IOS-1 Fix crash", "IOS-2"])
+                .as_deref(),
+            Some("IOS-1")
+        );
+        assert_eq!(pattern.find_in(&["main", "Update README"]), None);
+    }
+
+    #[test]
+    fn references() {
+        let pattern = TicketPattern::default();
+
+        assert!(pattern.references("IOS-1, IOS-2: Fix crashes", "IOS-2"));
+        assert!(pattern.references("feature/IOS-2-fix", "ios-2"));
+        assert!(!pattern.references("IOS-21: Fix crash", "IOS-2"));
+    }
+}
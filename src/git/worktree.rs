@@ -0,0 +1,196 @@
+use std::path::{Path, PathBuf};
+
+use git2::{
+    BranchType, ErrorClass, ErrorCode, Oid, Remote, Repository, Worktree, WorktreeAddOptions,
+    WorktreeLockStatus, WorktreePruneOptions,
+};
+
+use super::{find_remote_branch, has_uncommitted_changes, switch_to_branch};
+use crate::Error;
+
+/// Names of the worktrees `pr checkout --worktree` adds are this followed by the PR id.
+const WORKTREE_PREFIX: &str = "pr-";
+
+pub struct PrWorktree {
+    pub id: u16,
+    pub name: String,
+    pub path: PathBuf,
+    pub branch: Option<String>,
+}
+
+/// Fills `{repo}` and `{id}` in a worktree path template like `../{repo}-pr-{id}`,
+/// relative paths are relative to the work tree of the main repository.
+pub fn worktree_path(template: &str, id: u16, repo: &Repository) -> PathBuf {
+    let workdir = if repo.is_worktree() {
+        // the git directory of a linked worktree is `.git/worktrees/<name>` of the main one
+        repo.path().ancestors().nth(3)
+    } else {
+        repo.workdir()
+    };
+
+    resolve_worktree_path(template, id, workdir.unwrap_or(repo.path()))
+}
+
+fn resolve_worktree_path(template: &str, id: u16, workdir: &Path) -> PathBuf {
+    let repo_name = workdir
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+
+    let path = template
+        .replace("{repo}", &repo_name)
+        .replace("{id}", &id.to_string());
+
+    workdir.join(path)
+}
+
+/// Switches the linked worktree of PR `id` to `branch_name`, adding it at `path` first
+/// when the PR has none yet. Returns where the worktree is.
+pub fn checkout_pr_worktree(
+    id: u16,
+    branch_name: &str,
+    commit: &str,
+    remote: &Remote,
+    path: &Path,
+    repo: &Repository,
+) -> Result<PathBuf, Error> {
+    let name = format!("{WORKTREE_PREFIX}{id}");
+
+    // the worktree opens the remote by name, an anonymous one isn't there
+    let remote_name = remote.name().ok_or(Error::AnonymousRemote)?;
+
+    let worktree = match repo.find_worktree(&name) {
+        Ok(worktree) if worktree.validate().is_ok() => {
+            let worktree_repo = Repository::open_from_worktree(&worktree)?;
+
+            if has_uncommitted_changes(&worktree_repo)? {
+                return Err(Error::WorktreeHasChanges(worktree.path().to_path_buf()));
+            }
+
+            worktree
+        }
+        Ok(worktree) => {
+            // the directory was removed without git knowing about it
+            worktree.prune(None)?;
+            add_worktree(&name, branch_name, commit, remote, path, repo)?
+        }
+        Err(err) if err.code() == ErrorCode::NotFound => {
+            add_worktree(&name, branch_name, commit, remote, path, repo)?
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let worktree_repo = Repository::open_from_worktree(&worktree)?;
+    let worktree_remote = worktree_repo.find_remote(remote_name)?;
+
+    switch_to_branch(branch_name, commit, &worktree_remote, &worktree_repo)?;
+
+    Ok(worktree.path().to_path_buf())
+}
+
+fn add_worktree(
+    name: &str,
+    branch_name: &str,
+    commit: &str,
+    remote: &Remote,
+    path: &Path,
+    repo: &Repository,
+) -> Result<Worktree, Error> {
+    // the branch is checked out by the new worktree, not by `repo`
+    let branch = match repo.find_branch(branch_name, BranchType::Local) {
+        Ok(branch) => branch,
+        Err(err) if err.class() == ErrorClass::Reference && err.code() == ErrorCode::NotFound => {
+            match find_remote_branch(branch_name, remote, repo) {
+                Ok(remote_branch) => {
+                    let target = remote_branch.get().peel_to_commit()?;
+                    let mut branch = repo.branch(branch_name, &target, false)?;
+                    branch.set_upstream(remote_branch.name()?)?;
+                    branch
+                }
+                Err(err) if err.code() == ErrorCode::NotFound => {
+                    let target = repo.find_commit(Oid::from_str(commit)?)?;
+                    repo.branch(branch_name, &target, false)?
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    println!("adding worktree {}", path.display());
+
+    let mut options = WorktreeAddOptions::new();
+    options.reference(Some(branch.get()));
+
+    Ok(repo.worktree(name, path, Some(&options))?)
+}
+
+/// Worktrees added by `pr checkout --worktree`, ordered by PR id.
+pub fn pr_worktrees(repo: &Repository) -> Result<Vec<PrWorktree>, Error> {
+    let names = repo.worktrees()?;
+
+    let mut worktrees = names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let id = name.strip_prefix(WORKTREE_PREFIX)?.parse().ok()?;
+            let worktree = repo.find_worktree(name).ok()?;
+
+            let branch = Repository::open_from_worktree(&worktree)
+                .ok()
+                .and_then(|repo| super::get_current_branch(&repo));
+
+            Some(PrWorktree {
+                id,
+                name: name.to_string(),
+                path: worktree.path().to_path_buf(),
+                branch,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    worktrees.sort_unstable_by_key(|worktree| worktree.id);
+
+    Ok(worktrees)
+}
+
+/// Deletes the directory of the worktree and forgets about it, its branch is kept.
+pub fn remove_pr_worktree(worktree: &PrWorktree, repo: &Repository) -> Result<(), Error> {
+    let found = repo.find_worktree(&worktree.name)?;
+
+    if found.is_locked()? != WorktreeLockStatus::Unlocked {
+        return Err(Error::WorktreeLocked(worktree.path.clone()));
+    }
+
+    if found.validate().is_ok() {
+        let worktree_repo = Repository::open_from_worktree(&found)?;
+
+        if has_uncommitted_changes(&worktree_repo)? {
+            return Err(Error::WorktreeHasChanges(worktree.path.clone()));
+        }
+    }
+
+    let mut options = WorktreePruneOptions::new();
+    options.valid(true).working_tree(true);
+
+    Ok(found.prune(Some(&mut options))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let workdir = Path::new("/src/gitext");
+
+        assert_eq!(
+            resolve_worktree_path("../{repo}-pr-{id}", 12, workdir),
+            Path::new("/src/gitext/../gitext-pr-12")
+        );
+        assert_eq!(
+            resolve_worktree_path("/tmp/reviews/{id}", 7, workdir),
+            Path::new("/tmp/reviews/7")
+        );
+    }
+}
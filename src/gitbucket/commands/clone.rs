@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::bitbucket::{Client, RepoId};
+use crate::cli::CloneTarget;
+use crate::error::Error;
+use crate::git::{clone_repo, set_config, Config};
+
+pub struct Clone;
+
+impl Clone {
+    pub async fn handle(args: &ArgMatches, config: &Config, path: &Path) -> Result<(), Error> {
+        let target: &CloneTarget = args.get_one("repo").unwrap();
+        let repo_id = target
+            .segments(&config.base_url)
+            .and_then(|segments| RepoId::from_path(&segments))
+            .ok_or_else(|| Error::NotProviderRepo(target.to_string(), config.provider))?;
+
+        let client = Client::new(config);
+        let repository = client.get_repo(&repo_id).await?;
+
+        let url = repository
+            .clone_url()
+            .ok_or_else(|| Error::NotProviderRepo(target.to_string(), config.provider))?;
+
+        let mut path = path.to_path_buf();
+        path.push(&repository.slug);
+
+        let path = path.as_path();
+
+        let repo = clone_repo(url, path, config)?;
+        set_config(&repo, config)?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::bitbucket::{Client, RepoId};
-use crate::git::{AuthDomainConfig, BaseUrlConfig};
+use crate::git::{get_current_branch, push_branch, AuthDomainConfig, BaseUrlConfig};
 use crate::Error;
 
 use clap::ArgMatches;
@@ -41,27 +41,15 @@ impl Create {
             println!("Create a repository {}", link.href);
         }
 
-        let mut url = None;
+        if let Some(url) = repository.clone_url() {
+            let mut remote = repo.remote("bitbucket", url)?;
 
-        for link in &repository.links.clone {
-            if let Some("ssh") = link.name.as_deref() {
-                url = Some(link.href.as_str());
-                break;
+            if args.get_flag("push") {
+                let branch = get_current_branch(repo).ok_or(Error::Detached)?;
+                push_branch(&mut remote, &branch, true, false, repo, config)?;
             }
         }
 
-        if let None = url {
-            url = repository
-                .links
-                .clone
-                .first()
-                .map(|link| link.href.as_str());
-        }
-
-        if let Some(url) = url {
-            repo.remote("bitbucket", url)?;
-        }
-
         Ok(())
     }
 }
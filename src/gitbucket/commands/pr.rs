@@ -1,14 +1,22 @@
 use std::collections::HashMap;
 use std::process::{Command, Stdio};
 
-use crate::bitbucket::{get_bitbucket_remote, get_current_repo_id, Client, PullRequest, RepoId};
+use crate::bitbucket::{
+    get_bitbucket_remote, get_current_repo_id, Client, PullRequest, PullRequestState, RepoId,
+};
 use crate::error::Error;
 use crate::git::{
-    fetch_remote, get_current_branch, switch_to_branch, AuthDomainConfig, BaseUrlConfig,
-    JiraUrlConfig,
+    checkout_pr_worktree, fetch_remote, get_current_branch, get_head_commit_summary, pr_worktrees,
+    switch_with_autostash, sync_branch, worktree_path, AuthDomainConfig, AutostashConfig,
+    BaseUrlConfig, FetchSpec, JiraLinkConfig, JiraTransitionsConfig, JiraUrlConfig,
+    RequiredApprovalsConfig, TicketPatternConfig, TrackerConfig, WorktreeConfig,
 };
+use crate::jira;
+use crate::view::{self, Action, Column, Output};
+use crate::Worktrees;
 
 use clap::ArgMatches;
+use futures::future::join_all;
 use git2::Repository;
 use url::Url;
 
@@ -24,6 +32,13 @@ impl Pr {
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: JiraTransitionsConfig,
+        Conf: JiraLinkConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+        Conf: AutostashConfig,
+        Conf: RequiredApprovalsConfig,
+        Conf: WorktreeConfig,
     {
         let repo_id = get_current_repo_id(&repo, config).ok_or(Error::InvalidRepo)?;
         let branch = get_current_branch(&repo).ok_or(Error::Detached)?;
@@ -53,50 +68,57 @@ impl Pr {
                 let pr = client
                     .get_pr_by_id(id, &repo_id)
                     .await
-                    .map_err(|err| Error::NoPrWithId(id, err))?;
+                    .map_err(|err| Error::NoPrWithId(id, err.into()))?;
+
+                if args.get_flag("worktree") {
+                    return Self::checkout_worktree(id, &pr, args, repo, config);
+                }
 
-                Self::switch(&pr, &repo, config)?;
+                Self::switch(&pr, args, repo, config)?;
 
                 Ok(())
             }
             ("info", args) => {
                 let client = Client::new(config);
 
-                if let Some(id) = args.get_one::<u16>("id") {
-                    let pr = client.get_pr_by_id(*id, &repo_id).await?;
-                    let build_stats = client
-                        .get_commits_build_stats(&[&pr.from_ref.latest_commit])
-                        .await?
-                        .into_iter()
-                        .map(|(k, v)| (k, v.into()))
-                        .collect::<HashMap<_, _>>();
-
-                    super::prs::Prs::print_table_for_prs(&[pr], build_stats, config).await;
+                let mut columns = Column::defaults(config);
+                columns.push(Column::State);
+                let output = Output::from_args(args, None, columns)?;
 
-                    Ok(())
+                let prs = if let Some(id) = args.get_one::<u16>("id") {
+                    vec![client.get_pr_by_id(*id, &repo_id).await?]
                 } else {
                     let mut prs = client
                         .find_prs_for_branch(&branch, &repo_id, "OPEN")
                         .await?;
                     prs.sort_unstable_by_key(|pr| std::cmp::Reverse(pr.id));
+                    prs
+                };
+
+                let shas = prs
+                    .iter()
+                    .map(|pr| pr.from_ref.latest_commit.as_str())
+                    .collect::<Vec<_>>();
+
+                let build_stats = client
+                    .get_commits_build_stats(&shas)
+                    .await?
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect::<HashMap<_, _>>();
+
+                let views =
+                    super::prs::Prs::views_for_prs(&prs, &repo_id, &build_stats, config, true)
+                        .await;
+                output.print(&views, "No PRs for that branch");
 
-                    let shas = prs
-                        .iter()
-                        .map(|pr| pr.from_ref.latest_commit.as_str())
-                        .collect::<Vec<_>>();
-
-                    let build_stats = client
-                        .get_commits_build_stats(&shas)
-                        .await?
-                        .into_iter()
-                        .map(|(k, v)| (k, v.into()))
-                        .collect::<HashMap<_, _>>();
-
-                    super::prs::Prs::print_table_for_prs(&prs, build_stats, config).await;
-
-                    Ok(())
-                }
+                Ok(())
             }
+            ("pick", args) => Self::pick(args, repo, config).await,
+            ("worktrees", args) => Self::worktrees(args, &repo_id, repo, config).await,
+            ("create", args) => Self::create(args, &branch, &repo_id, repo, config).await,
+            ("merge", args) => Self::merge(args, &branch, &repo_id, repo, config).await,
+            ("sync", args) => Self::sync(args, &branch, repo, config),
             ("new", args) => Self::open_url(Self::url_for_create(
                 &branch,
                 &repo_id,
@@ -117,6 +139,163 @@ impl Pr {
     }
 }
 
+impl Pr {
+    pub async fn pick<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+        Conf: AutostashConfig,
+        Conf: RequiredApprovalsConfig,
+    {
+        let views = super::prs::Prs::collect(args, repo, config, false).await?;
+
+        let (pr, action) = match view::pick(&views)? {
+            Some(picked) => picked,
+            None if views.is_empty() => {
+                println!("No open PRs in that repo");
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+
+        match action {
+            Action::Checkout => {
+                let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+
+                let client = Client::new(config);
+                let pr = client
+                    .get_pr_by_id(pr.id, &repo_id)
+                    .await
+                    .map_err(|err| Error::NoPrWithId(pr.id, err.into()))?;
+
+                Self::switch(&pr, args, repo, config)
+            }
+            Action::Browse => Self::open_url(pr.url.clone()),
+            Action::Info => {
+                let mut columns = Column::defaults(config);
+                columns.push(Column::State);
+
+                let output = Output::from_args(args, None, columns)?;
+                output.print(std::slice::from_ref(pr), "");
+
+                Ok(())
+            }
+            Action::CopyUrl => view::copy_to_clipboard(pr.url.as_str()),
+        }
+    }
+}
+
+impl Pr {
+    async fn create<Conf>(
+        args: &ArgMatches,
+        branch: &str,
+        repo_id: &RepoId,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: JiraTransitionsConfig,
+        Conf: JiraLinkConfig,
+        Conf: TicketPatternConfig,
+    {
+        let target = args.get_one::<String>("target").expect("required");
+        let title = match args.get_one::<String>("title") {
+            Some(title) => title.clone(),
+            None => get_head_commit_summary(repo).unwrap_or_else(|| branch.to_string()),
+        };
+
+        if !args.get_flag("no-push") {
+            let mut remote = get_bitbucket_remote(repo, config).ok_or(Error::NoRemote)?;
+            sync_branch(&mut remote, branch, false, repo, config)?;
+        }
+
+        let client = Client::new(config);
+        let pr = client
+            .create_pr(repo_id, &title, branch, target, args.get_flag("draft"))
+            .await?;
+
+        println!("Created PR #{}: {}", pr.id, pr.url(config.base_url()));
+
+        let key = config
+            .ticket_pattern()
+            .find_for_branch(branch, Some(&title), Some(repo));
+        jira::run_transition_hook(key.as_deref(), config.jira_on_pr_create(), config).await;
+
+        let link = jira::PrLink {
+            id: format!("#{}", pr.id),
+            title: &title,
+            url: &pr.url(config.base_url()),
+            state: view::PullRequestState::Open.as_str(),
+        };
+        jira::run_link_hook(key.as_deref(), &link, config).await;
+
+        Ok(())
+    }
+
+    async fn merge<Conf>(
+        args: &ArgMatches,
+        branch: &str,
+        repo_id: &RepoId,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: JiraTransitionsConfig,
+        Conf: JiraLinkConfig,
+        Conf: TicketPatternConfig,
+    {
+        let client = Client::new(config);
+
+        let pr = match args.get_one::<u16>("id") {
+            Some(id) => client
+                .get_pr_by_id(*id, repo_id)
+                .await
+                .map_err(|err| Error::NoPrWithId(*id, err.into()))?,
+            None => Self::find_existing_open_pr(branch, repo_id, config)
+                .await?
+                .ok_or_else(|| Error::NoOpenPrForBranch(branch.to_string()))?,
+        };
+
+        let merged = client.merge_pr(pr.id, pr.version, repo_id).await?;
+        if merged.state != PullRequestState::Merged {
+            return Err(Error::NotMerged(pr.id, "it's still open".to_string()));
+        }
+
+        println!("Merged PR #{}: {}", pr.id, pr.title);
+
+        // only the current branch's commits are available locally
+        let repo = Some(repo).filter(|_| pr.from_ref.display_id == branch);
+        let key =
+            config
+                .ticket_pattern()
+                .find_for_branch(&pr.from_ref.display_id, Some(&pr.title), repo);
+        jira::run_transition_hook(key.as_deref(), config.jira_on_merge(), config).await;
+
+        let link = jira::PrLink {
+            id: format!("#{}", pr.id),
+            title: &pr.title,
+            url: &pr.url(config.base_url()),
+            state: view::PullRequestState::Merged.as_str(),
+        };
+        jira::run_link_hook(key.as_deref(), &link, config).await;
+
+        Ok(())
+    }
+}
+
 impl Pr {
     async fn find_existing_open_pr<Conf>(
         branch: &str,
@@ -130,7 +309,7 @@ impl Pr {
         let client = Client::new(config);
         let prs = client.find_prs_for_branch(&branch, &repo_id, "OPEN").await;
 
-        let prs = prs.map_err(|err| Error::NoPrsForBranch(branch.to_string(), err))?;
+        let prs = prs.map_err(|err| Error::NoPrsForBranch(branch.to_string(), err.into()))?;
         Ok(prs.into_iter().next())
     }
 
@@ -176,17 +355,96 @@ impl Pr {
 }
 
 impl Pr {
-    fn switch<Conf>(pr: &PullRequest, repo: &Repository, config: &Conf) -> Result<(), Error>
+    fn sync<Conf>(
+        args: &ArgMatches,
+        branch: &str,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: BaseUrlConfig,
+        Conf: AuthDomainConfig,
+    {
+        let mut remote = get_bitbucket_remote(repo, config).ok_or(Error::NoRemote)?;
+        sync_branch(&mut remote, branch, args.get_flag("force"), repo, config)
+    }
+
+    fn switch<Conf>(
+        pr: &PullRequest,
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
     where
         Conf: BaseUrlConfig,
         Conf: AuthDomainConfig,
+        Conf: AutostashConfig,
     {
         let branch_name = &pr.from_ref.display_id;
         let commit_sha = &pr.from_ref.latest_commit;
 
         let mut remote = get_bitbucket_remote(&repo, config).unwrap();
-        fetch_remote(&mut remote, repo, config)?;
+        let fetch = FetchSpec::branch(branch_name, args.get_flag("prune"));
+        fetch_remote(&mut remote, fetch, repo, config)?;
 
-        switch_to_branch(branch_name, commit_sha, &remote, repo)
+        let autostash = args.get_flag("autostash") || config.autostash();
+        switch_with_autostash(branch_name, commit_sha, &remote, repo, autostash)
+    }
+
+    fn checkout_worktree<Conf>(
+        id: u16,
+        pr: &PullRequest,
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: BaseUrlConfig,
+        Conf: AuthDomainConfig,
+        Conf: WorktreeConfig,
+    {
+        let mut remote = get_bitbucket_remote(repo, config).unwrap();
+        let fetch = FetchSpec::branch(&pr.from_ref.display_id, args.get_flag("prune"));
+        fetch_remote(&mut remote, fetch, repo, config)?;
+
+        let path = worktree_path(config.worktree_path(), id, repo);
+        let path = checkout_pr_worktree(
+            id,
+            &pr.from_ref.display_id,
+            &pr.from_ref.latest_commit,
+            &remote,
+            &path,
+            repo,
+        )?;
+
+        println!("PR #{id} is checked out in {}", path.display());
+
+        Ok(())
+    }
+
+    async fn worktrees<Conf>(
+        args: &ArgMatches,
+        repo_id: &RepoId,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        let worktrees = pr_worktrees(repo)?;
+
+        let client = Client::new(config);
+        let states = join_all(
+            worktrees
+                .iter()
+                .map(|worktree| client.get_pr_by_id(worktree.id, repo_id)),
+        )
+        .await
+        .into_iter()
+        .map(|pr| pr.ok().map(|pr| super::prs::Prs::view_state(&pr)))
+        .collect();
+
+        Worktrees::handle(args, worktrees, states, repo)
     }
 }
@@ -1,13 +1,22 @@
 use std::collections::HashMap;
 
-use crate::bitbucket::{get_current_repo_id, Client, MergedBuildStatus, PullRequest};
+use crate::bitbucket::{
+    get_current_repo_id, Client, MergeStatus, MergedBuildStatus, ParticipantRole, PullRequest,
+    PullRequestState, RepoId,
+};
 use crate::error::Error;
-use crate::git::{extract_ticket, AuthDomainConfig, BaseUrlConfig, JiraUrlConfig};
+use crate::git::{
+    AuthDomainConfig, BaseUrlConfig, JiraUrlConfig, PrsColumnsConfig, RequiredApprovalsConfig,
+    TicketPatternConfig, TrackerConfig,
+};
+use crate::view::{
+    self, ApprovalsView, CiStatus, Column, Mergeability, Output, PullRequestView, TicketView,
+    UserView,
+};
 
 use clap::ArgMatches;
+use futures_util::{stream, StreamExt};
 use git2::Repository;
-use jira_api::JiraClient;
-use prettytable::{cell, row, Cell, Table};
 
 pub struct Prs;
 
@@ -21,137 +30,174 @@ impl Prs {
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: RequiredApprovalsConfig,
+        Conf: PrsColumnsConfig,
+        Conf: TrackerConfig,
     {
-        let repo_id = get_current_repo_id(&repo, config).ok_or(Error::InvalidRepo)?;
+        // Bitbucket lists PRs without their merge status, it takes a request for each PR
+        let mut defaults = Column::defaults(config);
+        defaults.retain(|column| *column != Column::Merge);
 
-        // TODO: handle `assigned` and username
-        let author = match args.get_one::<String>("filter").map(String::as_str) {
-            Some("my") => {
-                let (username, _) = chipp_auth::user_and_password(config.auth_domain());
-                Some(username)
-            }
-            Some("assigned") => unimplemented!(),
-            Some(_username) => unimplemented!(),
-            None => None,
-        };
+        let output = Output::from_args(args, config.prs_columns(), defaults)?;
+        let views = Self::collect(args, repo, config, output.selects(Column::Merge)).await?;
 
-        let client = Client::new(config);
-        let prs = client.find_open_prs(&repo_id, author).await?;
+        output.print(&views, "No open PRs in that repo");
 
-        let shas = prs
-            .values
-            .iter()
-            .map(|pr| pr.from_ref.latest_commit.as_str())
-            .collect::<Vec<_>>();
+        Ok(())
+    }
 
-        let build_statuses = client
-            .get_commits_build_stats(&shas)
-            .await?
-            .into_iter()
-            .map(|(k, v)| (k, v.into()))
-            .collect::<HashMap<_, _>>();
+    /// Open PRs of the repository, `mergeability` fetches the merge status of each one.
+    pub async fn collect<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+        mergeability: bool,
+    ) -> Result<Vec<PullRequestView>, Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: RequiredApprovalsConfig,
+    {
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let participant = Self::participant_filter(args, config);
 
-        Self::print_table_for_prs(&prs.values, build_statuses, config).await;
+        let client = Client::new(config);
+        let prs = client.find_open_prs(&repo_id, participant).await?;
 
-        Ok(())
+        let build_statuses = Self::get_build_statuses_for_prs(&prs.values, &client).await?;
+
+        Ok(Self::views_for_prs(&prs.values, &repo_id, &build_statuses, config, mergeability).await)
     }
 
-    pub async fn print_table_for_prs<Conf>(
-        prs: &[PullRequest],
-        build_statuses: HashMap<String, MergedBuildStatus>,
+    /// PRs whose branch or title references the ticket `key`.
+    pub async fn find_for_ticket<Conf>(
+        key: &str,
+        repo: &Repository,
         config: &Conf,
-    ) where
+    ) -> Result<Vec<PullRequestView>, Error>
+    where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: RequiredApprovalsConfig,
     {
-        if prs.is_empty() {
-            println!("No PRs for that branch");
-            return;
-        }
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
 
-        let mut table = Table::new();
-        table.set_titles(row![
-            "ID",
-            "Author",
-            "Title",
-            "CI",
-            "Approvals",
-            "Target",
-            "Last updated",
-            "Jira status"
-        ]);
-
-        let tickets = Self::get_tickets_statuses_for_prs(&prs, config)
-            .await
-            .unwrap_or_default();
-        let na = String::from("N/A");
-
-        for pr in prs {
-            let mut row = row![
-                pr.id,
-                pr.author.user.display_name,
-                Self::title_for_pr(&pr, 35)
-            ];
-
-            let updated = pr.updated - chrono::Utc::now();
-            let updated = chrono_humanize::HumanTime::from(updated);
-
-            match build_statuses.get(&pr.from_ref.latest_commit) {
-                Some(MergedBuildStatus::Success) => row.add_cell(cell!(Fg->"A")),
-                Some(MergedBuildStatus::InProgress) => row.add_cell(cell!(Fy->"I")),
-                Some(MergedBuildStatus::Failed) | None => row.add_cell(cell!(Fr->"X")),
-            };
-
-            let approvals = pr
-                .reviewers
-                .iter()
-                .filter(|reviewer| {
-                    &reviewer.user.name != "devops"
-                        && &reviewer.user.name != "ci"
-                        && reviewer.approved
-                })
-                .collect::<Vec<_>>()
-                .len();
-
-            let reviewers = pr
-                .reviewers
-                .iter()
-                .filter(|reviewer| &reviewer.user.name != "devops" && &reviewer.user.name != "ci")
-                .collect::<Vec<_>>()
-                .len();
+        let pattern = config.ticket_pattern();
+        let mut prs = vec![];
+        let mut start = 0;
 
-            let approvals_cell = Cell::new(&format!("{}/{}", approvals, reviewers));
+        loop {
+            // `ALL` includes open PRs too
+            let page = client.find_closed_prs(&repo_id, "ALL", start).await?;
 
-            if approvals >= 2 {
-                row.add_cell(approvals_cell.style_spec("Fg"));
-            } else {
-                row.add_cell(approvals_cell.style_spec("Fr"));
+            prs.extend(page.values.into_iter().filter(|pr| {
+                pattern.references(&pr.from_ref.display_id, key)
+                    || pattern.references(&pr.title, key)
+            }));
+
+            match page.next_page_start {
+                Some(next_page_start) if !page.is_last_page => start = next_page_start,
+                _ => break,
             }
+        }
 
-            row.add_cell(cell!(pr.to_ref.display_id));
-            row.add_cell(cell!(updated));
+        let build_statuses = Self::get_build_statuses_for_prs(&prs, &client).await?;
 
-            let status = extract_ticket(&pr.from_ref.id)
-                .and_then(|ticket| tickets.get(ticket))
-                .unwrap_or(&na);
+        Ok(Self::views_for_prs(&prs, &repo_id, &build_statuses, config, false).await)
+    }
 
-            row.add_cell(cell!(status));
+    pub async fn views_for_prs<Conf>(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        build_statuses: &HashMap<String, MergedBuildStatus>,
+        config: &Conf,
+        mergeability: bool,
+    ) -> Vec<PullRequestView>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: RequiredApprovalsConfig,
+    {
+        let tickets = Self::get_tickets_statuses_for_prs(prs, config)
+            .await
+            .unwrap_or_default();
 
-            table.add_row(row);
-        }
+        let client = Client::new(config);
+        let merge_statuses = if mergeability {
+            stream::iter(
+                prs.iter()
+                    .map(|pr| client.get_pr_merge_status(pr.id, repo_id)),
+            )
+            .buffered(10)
+            .map(Result::ok)
+            .collect::<Vec<_>>()
+            .await
+        } else {
+            prs.iter().map(|_| None).collect()
+        };
 
-        table.printstd();
+        prs.iter()
+            .zip(merge_statuses)
+            .map(|(pr, merge_status)| {
+                Self::view_for_pr(
+                    pr,
+                    repo_id,
+                    build_statuses.get(&pr.from_ref.latest_commit),
+                    merge_status.as_ref(),
+                    &tickets,
+                    config,
+                )
+            })
+            .collect()
     }
 
-    fn title_for_pr(pr: &PullRequest, max_width: usize) -> String {
-        use hyphenation::{Language, Load, Standard};
-        use textwrap::{fill, Options, WordSplitter::Hyphenation};
+    fn participant_filter<Conf>(
+        args: &ArgMatches,
+        config: &Conf,
+    ) -> Option<(String, ParticipantRole)>
+    where
+        Conf: AuthDomainConfig,
+    {
+        // `switch` picks from all open PRs and has no filter argument
+        let filter = args.try_get_one::<String>("filter").ok().flatten();
 
-        let hyphenator = Standard::from_embedded(Language::EnglishUS).unwrap();
-        let options = Options::new(max_width).word_splitter(Hyphenation(hyphenator));
+        match filter.map(String::as_str) {
+            Some("my") => {
+                let (username, _) = chipp_auth::user_and_password(config.auth_domain());
+                Some((username, ParticipantRole::Author))
+            }
+            Some("assigned") => {
+                let (username, _) = chipp_auth::user_and_password(config.auth_domain());
+                Some((username, ParticipantRole::Reviewer))
+            }
+            Some(username) => Some((username.to_string(), ParticipantRole::Author)),
+            None => None,
+        }
+    }
 
-        fill(&pr.title, options)
+    async fn get_build_statuses_for_prs(
+        prs: &[PullRequest],
+        client: &Client<'_>,
+    ) -> Result<HashMap<String, MergedBuildStatus>, Error> {
+        let shas = prs
+            .iter()
+            .map(|pr| pr.from_ref.latest_commit.as_str())
+            .collect::<Vec<_>>();
+
+        Ok(client
+            .get_commits_build_stats(&shas)
+            .await?
+            .into_iter()
+            .map(|(k, v)| (k, v.into()))
+            .collect::<HashMap<_, _>>())
     }
 
     async fn get_tickets_statuses_for_prs<Conf>(
@@ -160,24 +206,115 @@ impl Prs {
     ) -> Option<HashMap<String, String>>
     where
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+    {
+        let keys = prs
+            .iter()
+            .filter_map(|pr| {
+                config
+                    .ticket_pattern()
+                    .find_in(&[&pr.from_ref.id, &pr.title])
+            })
+            .collect::<Vec<_>>();
+        let tickets = keys.iter().map(String::as_str).collect::<Vec<_>>();
+
+        crate::jira::statuses_for_tickets(&tickets, config).await
+    }
+
+    pub fn view_state(pr: &PullRequest) -> view::PullRequestState {
+        match pr.state {
+            PullRequestState::Open => view::PullRequestState::Open,
+            PullRequestState::Merged => view::PullRequestState::Merged,
+            PullRequestState::Declined => view::PullRequestState::Closed,
+        }
+    }
+
+    fn view_for_pr<Conf>(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        build_status: Option<&MergedBuildStatus>,
+        merge_status: Option<&MergeStatus>,
+        tickets: &HashMap<String, String>,
+        config: &Conf,
+    ) -> PullRequestView
+    where
+        Conf: BaseUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: RequiredApprovalsConfig,
     {
-        let jira_url = config.jira_url()?;
-        let jira_client = JiraClient::new(jira_url, jira_api::client::AuthType::AccessToken)?;
+        let state = Self::view_state(pr);
 
-        let mut tickets = prs
+        let ci = build_status.map(|status| match status {
+            MergedBuildStatus::Success => CiStatus::Passed,
+            MergedBuildStatus::InProgress => CiStatus::Running,
+            MergedBuildStatus::Failed => CiStatus::Failed,
+        });
+
+        let reviewers = pr
+            .reviewers
             .iter()
-            .filter_map(|pr| extract_ticket(&pr.from_ref.id))
+            .filter(|reviewer| &reviewer.user.name != "devops" && &reviewer.user.name != "ci")
             .collect::<Vec<_>>();
-        tickets.dedup();
 
-        let jql = format!("key in ({})", tickets.join(","));
-        let tickets = crate::jira::pull(&jira_client, &jql).await;
+        let approvals = ApprovalsView {
+            approved: reviewers
+                .iter()
+                .filter(|reviewer| reviewer.approved)
+                .count() as u16,
+            reviewers: Some(reviewers.len() as u16),
+            required: config.prs_required_approvals(),
+        };
 
-        Some(
-            tickets
-                .into_iter()
-                .map(|issue| (issue.key, issue.fields.status.name))
-                .collect::<HashMap<_, _>>(),
-        )
+        PullRequestView {
+            repository: format!("{}/{}", repo_id.project, repo_id.name),
+            id: pr.id,
+            title: pr.title.clone(),
+            url: pr.url(config.base_url()),
+            author: UserView {
+                login: pr.author.user.name.clone(),
+                name: Some(pr.author.user.display_name.clone()),
+            },
+            source: pr.from_ref.display_id.clone(),
+            target: pr.to_ref.display_id.clone(),
+            created: pr.created,
+            updated: pr.updated,
+            state,
+            ci,
+            approvals: Some(approvals),
+            mergeability: merge_status.map(Self::mergeability),
+            ticket: config
+                .ticket_pattern()
+                .find_in(&[&pr.from_ref.id, &pr.title])
+                .map(|key| TicketView::new(&key, tickets)),
+            labels: vec![],
+        }
+    }
+
+    fn mergeability(status: &MergeStatus) -> Mergeability {
+        if status.can_merge {
+            return Mergeability::Mergeable;
+        }
+
+        if status.conflicted {
+            return Mergeability::Conflicts;
+        }
+
+        // vetoes come from merge checks and only have human readable messages
+        let vetoes = status
+            .vetoes
+            .iter()
+            .map(|veto| veto.summary_message.to_lowercase())
+            .collect::<Vec<_>>();
+
+        if vetoes.iter().any(|veto| veto.contains("build")) {
+            Mergeability::BlockedByChecks
+        } else if vetoes
+            .iter()
+            .any(|veto| veto.contains("task") || veto.contains("comment"))
+        {
+            Mergeability::BlockedByDiscussions
+        } else {
+            Mergeability::Blocked
+        }
     }
 }
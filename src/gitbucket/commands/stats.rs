@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+
+use crate::bitbucket::{
+    get_current_repo_id, ActivityAction, Client, PullRequest, PullRequestState, RepoId,
+};
+use crate::git::{AuthDomainConfig, BaseUrlConfig};
+use crate::view::{self, Format, PullRequestHistory, PullRequestStats};
+use crate::Error;
+
+use clap::ArgMatches;
+use futures_util::{stream, StreamExt};
+use git2::Repository;
+
+pub struct Stats;
+
+impl Stats {
+    pub async fn handle<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        match args.subcommand() {
+            Some(("prs", args)) => Self::prs(args, repo, config).await,
+            _ => unreachable!("subcommand is required"),
+        }
+    }
+
+    async fn prs<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        let since = *args.get_one::<DateTime<Utc>>("since").expect("default");
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
+
+        let mut prs = Self::find_closed_prs(&client, &repo_id, "MERGED", since).await?;
+        prs.extend(Self::find_closed_prs(&client, &repo_id, "DECLINED", since).await?);
+
+        let history = stream::iter(
+            prs.iter()
+                .map(|pr| Self::history_for_pr(pr, &repo_id, &client)),
+        )
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await;
+
+        let repository = format!("{}/{}", repo_id.project, repo_id.name);
+        let stats = PullRequestStats::new(repository, since, &history);
+
+        view::print_stats(&stats, format);
+
+        Ok(())
+    }
+
+    async fn find_closed_prs(
+        client: &Client<'_>,
+        repo_id: &RepoId,
+        state: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<PullRequest>, Error> {
+        let mut result = vec![];
+        let mut start = 0;
+
+        loop {
+            let page = client.find_closed_prs(repo_id, state, start).await?;
+
+            // PRs are sorted newest first, nothing closed after `since` can follow
+            let is_last_page =
+                page.is_last_page || page.values.last().is_none_or(|pr| pr.updated < since);
+
+            start = page.start + page.size;
+
+            result.extend(
+                page.values
+                    .into_iter()
+                    .filter(|pr| pr.closed.is_some_and(|closed| closed >= since)),
+            );
+
+            if is_last_page {
+                return Ok(result);
+            }
+        }
+    }
+
+    async fn history_for_pr(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> PullRequestHistory {
+        let activities = client
+            .get_pr_activities(pr.id, repo_id)
+            .await
+            .map(|page| page.values)
+            .unwrap_or_default();
+
+        let reviews = activities
+            .iter()
+            .filter(|activity| activity.user.id != pr.author.user.id)
+            .filter(|activity| {
+                matches!(
+                    activity.action,
+                    ActivityAction::Approved | ActivityAction::Reviewed | ActivityAction::Commented
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut reviewers = reviews
+            .iter()
+            .map(|activity| activity.user.name.clone())
+            .collect::<Vec<_>>();
+        reviewers.sort_unstable();
+        reviewers.dedup();
+
+        let size = client
+            .get_pr_changes_count(pr.id, repo_id)
+            .await
+            .ok()
+            .map(u32::from);
+
+        let (ci_runs, ci_failures) = match client
+            .get_commit_build_stats(&pr.from_ref.latest_commit)
+            .await
+        {
+            Ok(stats) => {
+                let failures = stats.failed.unwrap_or_default();
+                let runs = failures
+                    + stats.successful.unwrap_or_default()
+                    + stats.cancelled.unwrap_or_default();
+
+                (runs, failures)
+            }
+            Err(_) => (0, 0),
+        };
+
+        let merged = match pr.state {
+            PullRequestState::Merged => pr.closed,
+            _ => None,
+        };
+
+        PullRequestHistory {
+            created: pr.created,
+            merged,
+            first_review: reviews.iter().map(|activity| activity.created_date).min(),
+            size,
+            reviewers,
+            ci_runs,
+            ci_failures,
+        }
+    }
+}
@@ -1,15 +1,19 @@
 mod commands {
     pub mod auth;
     pub mod browse;
+    pub mod clone;
     pub mod create;
     pub mod pr;
     pub mod prs;
+    pub mod stats;
     pub mod switch;
 }
 
 pub use commands::auth::Auth;
 pub use commands::browse::Browse;
+pub use commands::clone::Clone;
 pub use commands::create::Create;
 pub use commands::pr::Pr;
 pub use commands::prs::Prs;
+pub use commands::stats::Stats;
 pub use commands::switch::Switch;
@@ -1,12 +1,13 @@
 use chipp_http::curl::easy::{self, Auth};
-use chipp_http::{Error, HttpClient, Interceptor, Request};
-use serde::Serialize;
+use chipp_http::{Error, HttpClient, HttpMethod, Interceptor, Request};
+use serde::{Deserialize, Serialize};
 
 use crate::git::{AuthDomainConfig, BaseUrlConfig};
 use crate::Authenticator;
 
 use super::repo::Repo;
-use super::{CheckSuites, PullRequest, RepoId};
+use super::review::RequiredReviews;
+use super::{BranchRule, CheckSuites, Issue, PullRequest, RepoId, Review};
 
 pub struct Client<'a> {
     inner: HttpClient<Authenticator<'a>>,
@@ -57,6 +58,40 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn find_closed_prs(
+        &self,
+        repo_id: &RepoId,
+        page: u16,
+    ) -> Result<Vec<PullRequest>, Error> {
+        self.inner
+            .get_with_params(
+                &["repos", &repo_id.owner, &repo_id.repo, "pulls"],
+                &[
+                    ("state", "closed"),
+                    ("per_page", "100"),
+                    ("page", &format!("{page}")),
+                    ("sort", "updated"),
+                    ("direction", "desc"),
+                ],
+            )
+            .await
+    }
+
+    /// The 100 most recently updated PRs in any state.
+    pub async fn find_recent_prs(&self, repo_id: &RepoId) -> Result<Vec<PullRequest>, Error> {
+        self.inner
+            .get_with_params(
+                &["repos", &repo_id.owner, &repo_id.repo, "pulls"],
+                &[
+                    ("state", "all"),
+                    ("per_page", "100"),
+                    ("sort", "updated"),
+                    ("direction", "desc"),
+                ],
+            )
+            .await
+    }
+
     pub async fn get_commit_check_suites(
         &self,
         repo_id: &RepoId,
@@ -77,6 +112,57 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn get_pr_reviews(&self, pr_id: u16, repo_id: &RepoId) -> Result<Vec<Review>, Error> {
+        self.inner
+            .get_with_params(
+                &[
+                    "repos",
+                    &repo_id.owner,
+                    &repo_id.repo,
+                    "pulls",
+                    &format!("{pr_id}"),
+                    "reviews",
+                ],
+                &[("per_page", "100")],
+            )
+            .await
+    }
+
+    pub async fn get_branch_rules(
+        &self,
+        branch: &str,
+        repo_id: &RepoId,
+    ) -> Result<Vec<BranchRule>, Error> {
+        self.inner
+            .get(&[
+                "repos",
+                &repo_id.owner,
+                &repo_id.repo,
+                "rules",
+                "branches",
+                branch,
+            ])
+            .await
+    }
+
+    pub async fn get_required_reviews(
+        &self,
+        branch: &str,
+        repo_id: &RepoId,
+    ) -> Result<RequiredReviews, Error> {
+        self.inner
+            .get(&[
+                "repos",
+                &repo_id.owner,
+                &repo_id.repo,
+                "branches",
+                branch,
+                "protection",
+                "required_pull_request_reviews",
+            ])
+            .await
+    }
+
     pub async fn find_prs_for_branch(
         &self,
         branch: &str,
@@ -117,6 +203,123 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn create_pr(
+        &self,
+        repo_id: &RepoId,
+        title: &str,
+        head: &str,
+        base: &str,
+        draft: bool,
+    ) -> Result<PullRequest, Error> {
+        #[derive(Serialize)]
+        struct CreateBody<'a> {
+            title: &'a str,
+            head: &'a str,
+            base: &'a str,
+            draft: bool,
+        }
+
+        let mut request =
+            self.inner
+                .new_request(&["repos", &repo_id.owner, &repo_id.repo, "pulls"]);
+        request.set_json_body(&CreateBody {
+            title,
+            head,
+            base,
+            draft,
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    pub async fn merge_pr(&self, pr_id: u16, repo_id: &RepoId) -> Result<MergeResult, Error> {
+        let mut request = self.inner.new_request(&[
+            "repos",
+            &repo_id.owner,
+            &repo_id.repo,
+            "pulls",
+            &format!("{pr_id}"),
+            "merge",
+        ]);
+        request.set_json_body(&serde_json::json!({}));
+        request.set_method(HttpMethod::Put);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    /// Open issues of the repo assigned to `assignee`, PRs included.
+    pub async fn find_issues(&self, repo_id: &RepoId, assignee: &str) -> Result<Vec<Issue>, Error> {
+        self.inner
+            .get_with_params(
+                &["repos", &repo_id.owner, &repo_id.repo, "issues"],
+                &[
+                    ("state", "open"),
+                    ("assignee", assignee),
+                    ("per_page", "100"),
+                    ("sort", "updated"),
+                    ("direction", "desc"),
+                ],
+            )
+            .await
+    }
+
+    pub async fn get_issue(&self, number: u32, repo_id: &RepoId) -> Result<Issue, Error> {
+        self.inner
+            .get(&[
+                "repos",
+                &repo_id.owner,
+                &repo_id.repo,
+                "issues",
+                &format!("{number}"),
+            ])
+            .await
+    }
+
+    pub async fn create_issue(
+        &self,
+        repo_id: &RepoId,
+        title: &str,
+        body: Option<&str>,
+    ) -> Result<Issue, Error> {
+        #[derive(Serialize)]
+        struct CreateBody<'a> {
+            title: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            body: Option<&'a str>,
+        }
+
+        let mut request =
+            self.inner
+                .new_request(&["repos", &repo_id.owner, &repo_id.repo, "issues"]);
+        request.set_json_body(&CreateBody { title, body });
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    /// Closes the issue with the GraphQL API, REST needs a PATCH for that.
+    ///
+    /// GraphQL reports most failures, like an issue you can't access, with a 200 and `errors`.
+    pub async fn close_issue(&self, issue: &Issue) -> Result<GraphQlResult, Error> {
+        let mut request = self.inner.new_request(&["graphql"]);
+        request.set_json_body(&serde_json::json!({
+            "query": "mutation($id: ID!) { closeIssue(input: { issueId: $id }) { clientMutationId } }",
+            "variables": { "id": issue.node_id },
+        }));
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
     pub async fn get_repo(&self, repo_id: &RepoId) -> Result<Repo, Error> {
         self.inner
             .get(&["repos", &repo_id.owner, &repo_id.repo])
@@ -158,3 +361,20 @@ impl Client<'_> {
             .await
     }
 }
+
+#[derive(Deserialize)]
+pub struct MergeResult {
+    pub merged: bool,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct GraphQlResult {
+    #[serde(default)]
+    pub errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+pub struct GraphQlError {
+    pub message: String,
+}
@@ -0,0 +1,30 @@
+use super::user::User;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+pub struct Issue {
+    pub number: u32,
+    pub title: String,
+    pub state: String,
+
+    #[serde(rename = "html_url")]
+    pub url: Url,
+
+    pub assignee: Option<User>,
+    pub body: Option<String>,
+
+    /// Id for the GraphQL API
+    pub node_id: String,
+
+    /// The issues endpoints return PRs as well, they're the ones with this field
+    #[serde(default)]
+    pub pull_request: Option<IgnoredAny>,
+}
+
+impl Issue {
+    pub fn is_pr(&self) -> bool {
+        self.pull_request.is_some()
+    }
+}
@@ -3,15 +3,21 @@
 
 mod check_suite;
 mod client;
+mod issue;
 mod pull_request;
 mod repo;
 mod repo_id;
+mod review;
+mod tracker;
 mod user;
 
 pub use check_suite::{CheckSuites, Conclusion, Status};
-pub use client::Client;
-pub use pull_request::{PullRequest, State};
+pub use client::{Client, GraphQlResult, MergeResult};
+pub use issue::Issue;
+pub use pull_request::{MergeableState, PullRequest, State};
 pub use repo_id::RepoId;
+pub use review::{latest_review_states, BranchRule, Review, ReviewState};
+pub use tracker::GitHubTracker;
 
 use crate::git::{find_remote_by_priority, BaseUrlConfig};
 use git2::{Remote, Repository};
@@ -14,12 +14,32 @@ pub struct PullRequest {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub merged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
 
     pub user: User,
     pub state: State,
 
+    /// Only returned when a single PR is requested
+    #[serde(default)]
+    pub mergeable_state: Option<MergeableState>,
+    /// Only returned when a single PR is requested
+    #[serde(default)]
+    pub changed_files: Option<u32>,
+
     pub head: Ref,
     pub base: Ref,
+
+    #[serde(default)]
+    pub labels: Vec<Label>,
+
+    #[serde(default)]
+    pub requested_reviewers: Vec<User>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Label {
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,6 +51,20 @@ pub struct Ref {
     pub reference: String,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeableState {
+    Clean,
+    Dirty,
+    Behind,
+    Blocked,
+    Unstable,
+    HasHooks,
+    Draft,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum State {
@@ -50,13 +84,19 @@ mod tests {
             "id": "733000416u64",
             "number": 18u32,
             "state": "closed",
+            "mergeable_state": "dirty",
             "title": "Add staging",
             "user": {
                 "login": "chipp",
                 "id": 123u8
             },
+            "labels": [
+                { "id": 1u8, "name": "bug", "color": "d73a4a" }
+            ],
             "created_at": "2021-09-13T18:34:50Z",
             "updated_at": "2022-01-15T21:26:41Z",
+            "closed_at": "2022-01-15T21:26:41Z",
+            "changed_files": 4u8,
             "head": {
                 "label": "chipp:add-staging",
                 "ref": "add-staging",
@@ -102,12 +142,18 @@ mod tests {
             Utc.with_ymd_and_hms(2022, 1, 15, 21, 26, 41).unwrap()
         );
         assert_eq!(pr.merged_at, None);
+        assert_eq!(pr.closed_at, Some(pr.updated_at));
 
         assert_eq!(pr.user.id, 123);
         assert_eq!(pr.user.login, "chipp");
         assert_eq!(pr.user.name, None);
 
         assert_eq!(pr.state, State::Closed);
+        assert_eq!(pr.mergeable_state, Some(MergeableState::Dirty));
+        assert_eq!(pr.changed_files, Some(4));
+
+        assert_eq!(pr.labels.len(), 1);
+        assert_eq!(pr.labels[0].name, "bug");
 
         assert_eq!(pr.head.label, "chipp:add-staging");
         assert_eq!(pr.head.sha, "5b69861aec37ceb223a563ea85533a988f13fec6");
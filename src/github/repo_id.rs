@@ -16,6 +16,17 @@ impl RepoId {
             .ok_or(InvalidRepoId)
     }
 
+    /// From `owner/repo` or the path of a page of the repository, like `owner/repo/pulls`.
+    pub fn from_path(segments: &[&str]) -> Option<RepoId> {
+        match segments {
+            [owner, repo, ..] => Some(RepoId {
+                owner: owner.to_string(),
+                repo: repo.trim_end_matches(".git").to_string(),
+            }),
+            _ => None,
+        }
+    }
+
     fn from_url(url: &str, base_url: &Url) -> Option<RepoId> {
         let url = Url::parse(url).ok()?;
 
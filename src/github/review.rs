@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::user::User;
+
+#[derive(Deserialize)]
+pub struct Review {
+    pub id: u64,
+    pub user: Option<User>,
+    pub state: ReviewState,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+/// Latest review state of every reviewer, by user id.
+///
+/// Plain comments don't override an earlier approval or change request,
+/// the same way GitHub counts them.
+pub fn latest_review_states(reviews: &[Review]) -> HashMap<u32, ReviewState> {
+    let mut states = HashMap::new();
+
+    for review in reviews {
+        let user = match &review.user {
+            Some(user) => user,
+            None => continue,
+        };
+
+        match review.state {
+            ReviewState::Pending => {}
+            ReviewState::Commented => {
+                states.entry(user.id).or_insert(ReviewState::Commented);
+            }
+            state => {
+                states.insert(user.id, state);
+            }
+        }
+    }
+
+    states
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BranchRule {
+    PullRequest {
+        parameters: PullRequestRule,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+pub struct PullRequestRule {
+    pub required_approving_review_count: u16,
+}
+
+#[derive(Deserialize)]
+pub struct RequiredReviews {
+    #[serde(default)]
+    pub required_approving_review_count: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_states() {
+        let json = serde_json::json!([
+            {
+                "id": 1u8,
+                "user": { "login": "alice", "id": 1u8 },
+                "state": "CHANGES_REQUESTED",
+                "submitted_at": "2024-10-01T10:00:00Z"
+            },
+            {
+                "id": 2u8,
+                "user": { "login": "bob", "id": 2u8 },
+                "state": "APPROVED",
+                "submitted_at": "2024-10-01T11:00:00Z"
+            },
+            {
+                "id": 3u8,
+                "user": { "login": "alice", "id": 1u8 },
+                "state": "APPROVED",
+                "submitted_at": "2024-10-02T10:00:00Z"
+            },
+            {
+                "id": 4u8,
+                "user": { "login": "bob", "id": 2u8 },
+                "state": "COMMENTED",
+                "submitted_at": "2024-10-02T11:00:00Z"
+            },
+            {
+                "id": 5u8,
+                "user": { "login": "carol", "id": 3u8 },
+                "state": "COMMENTED",
+                "submitted_at": "2024-10-02T12:00:00Z"
+            },
+            {
+                "id": 6u8,
+                "user": null,
+                "state": "APPROVED",
+                "submitted_at": "2024-10-02T13:00:00Z"
+            }
+        ]);
+
+        let reviews: Vec<Review> = serde_json::from_value(json).unwrap();
+        let states = latest_review_states(&reviews);
+
+        assert_eq!(states.len(), 3);
+        assert_eq!(states[&1], ReviewState::Approved);
+        assert_eq!(states[&2], ReviewState::Approved);
+        assert_eq!(states[&3], ReviewState::Commented);
+    }
+
+    #[test]
+    fn branch_rules() {
+        let json = serde_json::json!([
+            { "type": "deletion" },
+            {
+                "type": "pull_request",
+                "parameters": {
+                    "required_approving_review_count": 2,
+                    "dismiss_stale_reviews_on_push": true
+                }
+            }
+        ]);
+
+        let rules: Vec<BranchRule> = serde_json::from_value(json).unwrap();
+
+        assert!(matches!(rules[0], BranchRule::Other));
+        assert!(matches!(
+            rules[1],
+            BranchRule::PullRequest { ref parameters } if parameters.required_approving_review_count == 2
+        ));
+    }
+}
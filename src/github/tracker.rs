@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use futures_util::{stream, StreamExt};
+use url::Url;
+
+use crate::tracker::{issue_number, IssueTracker, TrackedIssue};
+use crate::Error;
+
+use super::{Client, Issue, RepoId};
+
+pub struct GitHubTracker<'a> {
+    pub client: Client<'a>,
+    pub repo_id: &'a RepoId,
+    pub base_url: &'a Url,
+}
+
+impl GitHubTracker<'_> {
+    fn tracked(issue: Issue) -> TrackedIssue {
+        TrackedIssue {
+            key: format!("#{}", issue.number),
+            title: issue.title,
+            state: issue.state,
+            assignee: issue.assignee.map(|user| user.login),
+            description: issue.body,
+            url: issue.url,
+        }
+    }
+}
+
+impl IssueTracker for GitHubTracker<'_> {
+    async fn list(&self) -> Result<Vec<TrackedIssue>, Error> {
+        let user = self.client.whoami().await?;
+        let issues = self.client.find_issues(self.repo_id, &user.login).await?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| !issue.is_pr())
+            .map(Self::tracked)
+            .collect())
+    }
+
+    async fn get(&self, key: &str) -> Result<TrackedIssue, Error> {
+        let issue = self
+            .client
+            .get_issue(issue_number(key)?, self.repo_id)
+            .await?;
+
+        Ok(Self::tracked(issue))
+    }
+
+    async fn create(&self, title: &str, description: Option<&str>) -> Result<TrackedIssue, Error> {
+        let issue = self
+            .client
+            .create_issue(self.repo_id, title, description)
+            .await?;
+
+        Ok(Self::tracked(issue))
+    }
+
+    async fn close(&self, key: &str) -> Result<TrackedIssue, Error> {
+        let number = issue_number(key)?;
+        let issue = self.client.get_issue(number, self.repo_id).await?;
+        let result = self.client.close_issue(&issue).await?;
+
+        if !result.errors.is_empty() {
+            let messages = result
+                .errors
+                .into_iter()
+                .map(|err| err.message)
+                .collect::<Vec<_>>();
+
+            return Err(Error::IssueNotClosed(key.to_string(), messages.join("; ")));
+        }
+
+        self.get(key).await
+    }
+
+    async fn states(&self, keys: &[&str]) -> HashMap<String, String> {
+        let mut numbers = keys
+            .iter()
+            .filter_map(|key| issue_number(key).ok())
+            .collect::<Vec<_>>();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        // the issues endpoint can't filter by number, each issue takes a request of its own
+        let issues = stream::iter(numbers)
+            .map(
+                |number| async move { (number, self.client.get_issue(number, self.repo_id).await) },
+            )
+            .buffered(10)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut states = HashMap::new();
+
+        for (number, issue) in issues {
+            match issue {
+                Ok(issue) => {
+                    states.insert(number, issue.state);
+                }
+                Err(err) => {
+                    eprintln!("warning: can't get the state of #{number} from GitHub: {err}")
+                }
+            }
+        }
+
+        keys.iter()
+            .filter_map(|key| {
+                let state = states.get(&issue_number(key).ok()?)?;
+                Some((key.to_string(), state.clone()))
+            })
+            .collect()
+    }
+
+    fn url(&self, key: &str) -> Url {
+        let mut url = self.repo_id.url(self.base_url);
+
+        {
+            let mut segments = url.path_segments_mut().unwrap();
+            segments.extend(&["issues", key.trim_start_matches('#')]);
+        }
+
+        url
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+use super::user::User;
+
+#[derive(Deserialize)]
+pub struct Approvals {
+    #[serde(default)]
+    pub approvals_required: u16,
+    #[serde(default)]
+    pub approvals_left: u16,
+    pub approved_by: Vec<Approver>,
+}
+
+#[derive(Deserialize)]
+pub struct Approver {
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!({
+            "id": 5,
+            "iid": 5,
+            "project_id": 1,
+            "state": "opened",
+            "approved": false,
+            "approvals_required": 2,
+            "approvals_left": 1,
+            "approved_by": [
+                {
+                    "user": {
+                        "id": 61,
+                        "name": "Vladimir Burdukov",
+                        "username": "vladimir_burdukov"
+                    }
+                }
+            ]
+        });
+
+        let approvals: Approvals = serde_json::from_value(json).unwrap();
+
+        assert_eq!(approvals.approvals_required, 2);
+        assert_eq!(approvals.approvals_left, 1);
+        assert_eq!(approvals.approved_by.len(), 1);
+        assert_eq!(approvals.approved_by[0].user.name, "vladimir_burdukov");
+    }
+}
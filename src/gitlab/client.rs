@@ -3,9 +3,11 @@ use crate::{
     Authenticator,
 };
 
-use super::{user::User, Pipeline, PullRequest, RepoId};
+use super::{user::User, Approvals, Issue, Note, Pipeline, PullRequest, Repo, RepoId};
 
-use chipp_http::{Error, HttpClient};
+use chipp_http::{Error, HttpClient, HttpMethod};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 pub struct Client<'a> {
     inner: HttpClient<Authenticator<'a>>,
@@ -39,6 +41,10 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn get_repo(&self, repo_id: &RepoId) -> Result<Repo, Error> {
+        self.inner.get(vec!["projects", &repo_id.id()]).await
+    }
+
     pub async fn find_open_prs(
         &self,
         repo_id: &RepoId,
@@ -65,6 +71,40 @@ impl Client<'_> {
             .await
     }
 
+    pub async fn find_closed_prs(
+        &self,
+        repo_id: &RepoId,
+        state: &str,
+        updated_after: &DateTime<Utc>,
+        page: u8,
+    ) -> Result<Vec<PullRequest>, Error> {
+        self.inner
+            .get_with_params(
+                vec!["projects", &repo_id.id(), "merge_requests"],
+                &[
+                    ("state", state),
+                    ("updated_after", &updated_after.to_rfc3339()),
+                    ("per_page", "100"),
+                    ("page", &format!("{}", page)),
+                ],
+            )
+            .await
+    }
+
+    /// The 100 most recently updated MRs in any state.
+    pub async fn find_recent_prs(&self, repo_id: &RepoId) -> Result<Vec<PullRequest>, Error> {
+        self.inner
+            .get_with_params(
+                vec!["projects", &repo_id.id(), "merge_requests"],
+                &[
+                    ("state", "all"),
+                    ("order_by", "updated_at"),
+                    ("per_page", "100"),
+                ],
+            )
+            .await
+    }
+
     pub async fn find_prs_for_branch(
         &self,
         branch: &str,
@@ -102,4 +142,170 @@ impl Client<'_> {
             ])
             .await
     }
+
+    pub async fn create_pr(
+        &self,
+        repo_id: &RepoId,
+        title: &str,
+        source_branch: &str,
+        target_branch: &str,
+    ) -> Result<PullRequest, Error> {
+        #[derive(Serialize)]
+        struct CreateBody<'a> {
+            title: &'a str,
+            source_branch: &'a str,
+            target_branch: &'a str,
+        }
+
+        let mut request = self
+            .inner
+            .new_request(vec!["projects", &repo_id.id(), "merge_requests"]);
+        request.set_json_body(&CreateBody {
+            title,
+            source_branch,
+            target_branch,
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    pub async fn merge_pr(&self, id: u16, repo_id: &RepoId) -> Result<PullRequest, Error> {
+        let mut request = self.inner.new_request(vec![
+            "projects",
+            &repo_id.id(),
+            "merge_requests",
+            &id.to_string(),
+            "merge",
+        ]);
+        request.set_method(HttpMethod::Put);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    /// Open issues of the project assigned to the current user.
+    pub async fn find_issues(&self, repo_id: &RepoId) -> Result<Vec<Issue>, Error> {
+        self.inner
+            .get_with_params(
+                vec!["projects", &repo_id.id(), "issues"],
+                [
+                    ("state", "opened"),
+                    ("scope", "assigned_to_me"),
+                    ("per_page", "100"),
+                ],
+            )
+            .await
+    }
+
+    /// Issues of the project with ids from `ids`, in any state.
+    pub async fn find_issues_by_ids(
+        &self,
+        ids: &[u32],
+        repo_id: &RepoId,
+    ) -> Result<Vec<Issue>, Error> {
+        let ids = ids.iter().map(|id| ("iids[]", id.to_string()));
+
+        self.inner
+            .get_with_params(
+                vec!["projects", &repo_id.id(), "issues"],
+                ids.chain([("per_page", "100".to_string())]),
+            )
+            .await
+    }
+
+    pub async fn get_issue(&self, id: u32, repo_id: &RepoId) -> Result<Issue, Error> {
+        self.inner
+            .get(vec!["projects", &repo_id.id(), "issues", &id.to_string()])
+            .await
+    }
+
+    pub async fn create_issue(
+        &self,
+        repo_id: &RepoId,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<Issue, Error> {
+        #[derive(Serialize)]
+        struct CreateBody<'a> {
+            title: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'a str>,
+        }
+
+        let mut request = self
+            .inner
+            .new_request(vec!["projects", &repo_id.id(), "issues"]);
+        request.set_json_body(&CreateBody { title, description });
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    pub async fn close_issue(&self, id: u32, repo_id: &RepoId) -> Result<Issue, Error> {
+        let mut request =
+            self.inner
+                .new_request(vec!["projects", &repo_id.id(), "issues", &id.to_string()]);
+        request.set_json_body(&serde_json::json!({ "state_event": "close" }));
+        request.set_method(HttpMethod::Put);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    pub async fn get_pr_approvals(&self, id: u16, repo_id: &RepoId) -> Result<Approvals, Error> {
+        self.inner
+            .get(vec![
+                "projects",
+                &repo_id.id(),
+                "merge_requests",
+                &id.to_string(),
+                "approvals",
+            ])
+            .await
+    }
+
+    pub async fn get_pr_notes(&self, id: u16, repo_id: &RepoId) -> Result<Vec<Note>, Error> {
+        self.inner
+            .get_with_params(
+                vec![
+                    "projects",
+                    &repo_id.id(),
+                    "merge_requests",
+                    &id.to_string(),
+                    "notes",
+                ],
+                &[
+                    ("sort", "asc"),
+                    ("order_by", "created_at"),
+                    ("per_page", "100"),
+                ],
+            )
+            .await
+    }
+
+    pub async fn get_pr_pipelines(
+        &self,
+        id: u16,
+        repo_id: &RepoId,
+    ) -> Result<Vec<Pipeline>, Error> {
+        self.inner
+            .get_with_params(
+                vec![
+                    "projects",
+                    &repo_id.id(),
+                    "merge_requests",
+                    &id.to_string(),
+                    "pipelines",
+                ],
+                &[("per_page", "100")],
+            )
+            .await
+    }
 }
@@ -0,0 +1,65 @@
+use super::user::User;
+use serde::Deserialize;
+use url::Url;
+
+#[derive(Deserialize)]
+pub struct Issue {
+    #[serde(rename = "iid")]
+    pub id: u32,
+    pub title: String,
+    pub state: IssueState,
+
+    #[serde(rename = "web_url")]
+    pub url: Url,
+
+    pub assignee: Option<User>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueState {
+    Opened,
+    Closed,
+}
+
+impl IssueState {
+    /// The state the way GitHub and the PR tables call it.
+    pub fn name(self) -> &'static str {
+        match self {
+            IssueState::Opened => "open",
+            IssueState::Closed => "closed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parsing() {
+        let json = json!({
+            "id": 76,
+            "iid": 6,
+            "title": "Fix crash on launch",
+            "state": "opened",
+            "web_url": "https://gitlab.com/my-group/my-project/-/issues/6",
+            "assignee": {
+                "id": 61,
+                "name": "Vladimir Burdukov",
+                "username": "vladimir_burdukov",
+            },
+            "description": null,
+        });
+
+        let issue: Issue = serde_json::from_value(json).unwrap();
+
+        assert_eq!(issue.id, 6);
+        assert_eq!(issue.state, IssueState::Opened);
+        assert_eq!(issue.state.name(), "open");
+        assert_eq!(issue.assignee.unwrap().name, "vladimir_burdukov");
+        assert!(issue.description.is_none());
+    }
+}
@@ -9,11 +9,26 @@ mod client;
 pub use client::Client;
 
 mod pull_request;
-pub use pull_request::{PullRequest, PullRequestState};
+pub use pull_request::{DetailedMergeStatus, MergeStatus, PullRequest, PullRequestState};
 
 mod pipeline;
 pub use pipeline::{Pipeline, PipelineStatus};
 
+mod repo;
+pub use repo::Repo;
+
+mod approvals;
+pub use approvals::Approvals;
+
+mod note;
+pub use note::Note;
+
+mod issue;
+pub use issue::{Issue, IssueState};
+
+mod tracker;
+pub use tracker::GitLabTracker;
+
 mod user;
 
 use git2::{Remote, Repository};
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::user::User;
+
+#[derive(Deserialize)]
+pub struct Note {
+    pub id: u64,
+    pub author: User,
+    pub created_at: DateTime<Utc>,
+
+    /// Notes generated by GitLab itself, e.g. "added 1 commit"
+    pub system: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!({
+            "id": 302,
+            "body": "closed",
+            "author": {
+                "id": 1,
+                "username": "pipin",
+                "name": "Pip"
+            },
+            "created_at": "2013-10-02T09:22:45Z",
+            "system": true,
+            "noteable_id": 377,
+            "noteable_type": "MergeRequest"
+        });
+
+        let note: Note = serde_json::from_value(json).unwrap();
+
+        assert_eq!(note.id, 302);
+        assert_eq!(note.author.name, "pipin");
+        assert_eq!(
+            note.created_at,
+            Utc.with_ymd_and_hms(2013, 10, 2, 9, 22, 45).unwrap()
+        );
+        assert!(note.system);
+    }
+}
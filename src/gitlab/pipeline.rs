@@ -13,4 +13,8 @@ pub enum PipelineStatus {
     Running,
     Success,
     Failed,
+    Canceled,
+    Skipped,
+    #[serde(other)]
+    Other,
 }
@@ -16,8 +16,14 @@ pub struct PullRequest {
     pub created: DateTime<Utc>,
     #[serde(rename = "updated_at")]
     pub updated: DateTime<Utc>,
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
 
     pub author: User,
+    #[serde(default)]
+    pub reviewers: Vec<User>,
     pub state: PullRequestState,
 
     pub sha: String,
@@ -27,6 +33,47 @@ pub struct PullRequest {
     pub upvotes: u8,
     pub downvotes: u8,
     pub labels: Vec<String>,
+
+    pub merge_status: Option<MergeStatus>,
+    pub detailed_merge_status: Option<DetailedMergeStatus>,
+    #[serde(default)]
+    pub has_conflicts: bool,
+    pub blocking_discussions_resolved: Option<bool>,
+
+    /// Number of changed files, only returned when a single MR is requested.
+    /// It's a string because GitLab caps it, e.g. "1000+".
+    #[serde(default)]
+    pub changes_count: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStatus {
+    CanBeMerged,
+    CannotBeMerged,
+    CannotBeMergedRecheck,
+    Checking,
+    Unchecked,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailedMergeStatus {
+    Mergeable,
+    Conflict,
+    NeedRebase,
+    CiMustPass,
+    CiStillRunning,
+    DiscussionsNotResolved,
+    DraftStatus,
+    NotApproved,
+    BlockedStatus,
+    Checking,
+    Unchecked,
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Debug, Deserialize, PartialEq, PartialOrd, Eq, Ord)]
@@ -49,6 +96,7 @@ mod tests {
         let json = serde_json::json!(
           {
             "merge_status": "can_be_merged",
+            "detailed_merge_status": "not_approved",
             "author": {
               "username": "vpupkin",
               "id": 10,
@@ -68,6 +116,9 @@ mod tests {
             "source_branch": "some_feature",
             "upvotes": 3,
             "updated_at": "2021-04-27T16:41:32.410Z",
+            "merged_at": null,
+            "closed_at": null,
+            "changes_count": "12",
             "target_branch": "develop",
             "downvotes": 1,
             "title": "IOS-1212: Test title",
@@ -89,6 +140,10 @@ mod tests {
             Utc.with_ymd_and_hms(2021, 4, 27, 16, 41, 32).unwrap() + Duration::milliseconds(410)
         );
 
+        assert_eq!(pr.merged_at, None);
+        assert_eq!(pr.closed_at, None);
+        assert_eq!(pr.changes_count.as_deref(), Some("12"));
+
         assert_eq!(pr.author.id, 10);
         assert_eq!(pr.author.name, "vpupkin");
         assert_eq!(pr.author.display_name, "Vasili Pupkin");
@@ -106,5 +161,13 @@ mod tests {
         assert_eq!(pr.upvotes, 3);
         assert_eq!(pr.downvotes, 1);
         assert_eq!(pr.labels, vec!["2.21", "CI OK"]);
+
+        assert_eq!(pr.merge_status, Some(MergeStatus::CanBeMerged));
+        assert_eq!(
+            pr.detailed_merge_status,
+            Some(DetailedMergeStatus::NotApproved)
+        );
+        assert!(!pr.has_conflicts);
+        assert_eq!(pr.blocking_discussions_resolved, Some(true));
     }
 }
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Repo {
+    pub id: u64,
+    pub path_with_namespace: String,
+    pub web_url: String,
+    pub ssh_url_to_repo: String,
+    pub http_url_to_repo: String,
+}
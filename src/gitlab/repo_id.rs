@@ -35,6 +35,31 @@ impl RepoId {
         components.join("/")
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// From `group/subgroup/repo` or the path of a page of the repository, pages are
+    /// below `group/subgroup/repo/-/`.
+    pub fn from_path(segments: &[&str]) -> Option<RepoId> {
+        let end = segments
+            .iter()
+            .position(|segment| *segment == "-")
+            .unwrap_or(segments.len());
+
+        let mut project_path = segments[..end].to_vec();
+        let name = project_path.pop()?;
+
+        if project_path.is_empty() {
+            return None;
+        }
+
+        Some(RepoId {
+            project_path: project_path.into_iter().map(String::from).collect(),
+            name: String::from(name.trim_end_matches(".git")),
+        })
+    }
+
     fn from_url(url: &str, base_url: &Url) -> Option<RepoId> {
         let url = Url::parse(url).ok()?;
 
@@ -187,6 +212,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_from_path() {
+        assert_eq!(
+            RepoId::from_path(&["project", "subgroup", "ios", "-", "merge_requests", "12"]),
+            Some(RepoId {
+                project_path: vec!["project".to_string(), "subgroup".to_string()],
+                name: "ios".to_string()
+            })
+        );
+
+        assert_eq!(
+            RepoId::from_path(&["project", "ios.git"]).map(|repo_id| repo_id.id()),
+            Some("project/ios".to_string())
+        );
+
+        assert_eq!(RepoId::from_path(&["ios"]), None);
+    }
+
     #[test]
     fn url() {
         let repo_id = RepoId {
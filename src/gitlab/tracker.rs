@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::tracker::{issue_number, IssueTracker, TrackedIssue};
+use crate::Error;
+
+use super::{Client, Issue, RepoId};
+
+pub struct GitLabTracker<'a> {
+    pub client: Client<'a>,
+    pub repo_id: &'a RepoId,
+    pub base_url: &'a Url,
+}
+
+impl GitLabTracker<'_> {
+    fn tracked(issue: Issue) -> TrackedIssue {
+        TrackedIssue {
+            key: format!("#{}", issue.id),
+            title: issue.title,
+            state: issue.state.name().to_string(),
+            assignee: issue.assignee.map(|user| user.name),
+            description: issue.description,
+            url: issue.url,
+        }
+    }
+}
+
+impl IssueTracker for GitLabTracker<'_> {
+    async fn list(&self) -> Result<Vec<TrackedIssue>, Error> {
+        let issues = self.client.find_issues(self.repo_id).await?;
+        Ok(issues.into_iter().map(Self::tracked).collect())
+    }
+
+    async fn get(&self, key: &str) -> Result<TrackedIssue, Error> {
+        let issue = self
+            .client
+            .get_issue(issue_number(key)?, self.repo_id)
+            .await?;
+
+        Ok(Self::tracked(issue))
+    }
+
+    async fn create(&self, title: &str, description: Option<&str>) -> Result<TrackedIssue, Error> {
+        let issue = self
+            .client
+            .create_issue(self.repo_id, title, description)
+            .await?;
+
+        Ok(Self::tracked(issue))
+    }
+
+    async fn close(&self, key: &str) -> Result<TrackedIssue, Error> {
+        let issue = self
+            .client
+            .close_issue(issue_number(key)?, self.repo_id)
+            .await?;
+
+        Ok(Self::tracked(issue))
+    }
+
+    async fn states(&self, keys: &[&str]) -> HashMap<String, String> {
+        let ids = keys
+            .iter()
+            .filter_map(|key| issue_number(key).ok())
+            .collect::<Vec<_>>();
+
+        if ids.is_empty() {
+            return HashMap::new();
+        }
+
+        let issues = match self.client.find_issues_by_ids(&ids, self.repo_id).await {
+            Ok(issues) => issues,
+            Err(err) => {
+                eprintln!("warning: can't get issue states from GitLab: {err}");
+                return HashMap::new();
+            }
+        };
+
+        keys.iter()
+            .filter_map(|key| {
+                let id = issue_number(key).ok()?;
+                let issue = issues.iter().find(|issue| issue.id == id)?;
+                Some((key.to_string(), issue.state.name().to_string()))
+            })
+            .collect()
+    }
+
+    fn url(&self, key: &str) -> Url {
+        let mut url = self.repo_id.url(self.base_url);
+
+        {
+            let mut segments = url.path_segments_mut().unwrap();
+            segments.extend(&["-", "issues", key.trim_start_matches('#')]);
+        }
+
+        url
+    }
+}
@@ -1,5 +1,6 @@
 use crate::git::{AuthDomainConfig, BaseUrlConfig};
 use crate::gitlab::Client;
+use crate::view::{self, Format, UserView};
 
 use crate::Error;
 use chipp_http::{Error as HttpError, ErrorKind as HttpErrorKind};
@@ -7,12 +8,12 @@ use chipp_http::{Error as HttpError, ErrorKind as HttpErrorKind};
 pub struct Auth;
 
 impl Auth {
-    pub async fn handle<Conf>(config: &Conf) -> Result<(), Error>
+    pub async fn handle<Conf>(config: &Conf, format: Format) -> Result<(), Error>
     where
         Conf: BaseUrlConfig,
         Conf: AuthDomainConfig + Send + Sync,
     {
-        match Self::print_whoami(config).await {
+        match Self::print_whoami(config, format).await {
             Ok(_) => Ok(()),
             Err(HttpError {
                 request: _,
@@ -23,14 +24,14 @@ impl Auth {
 
                 chipp_auth::reset_token(config.auth_domain(), "access_token");
 
-                Self::print_whoami(config)
+                Self::print_whoami(config, format)
                     .await
                     .map_err(|_| Error::AuthorizationError)
             }
         }
     }
 
-    async fn print_whoami<Conf>(config: &Conf) -> Result<(), chipp_http::Error>
+    async fn print_whoami<Conf>(config: &Conf, format: Format) -> Result<(), chipp_http::Error>
     where
         Conf: BaseUrlConfig,
         Conf: AuthDomainConfig + Send + Sync,
@@ -38,7 +39,17 @@ impl Auth {
         let client = Client::new(config);
         match client.whoami().await {
             Ok(user) => {
-                println!("You're logged in as {}", user.display_name);
+                match format {
+                    Format::Table => println!("You're logged in as {}", user.display_name),
+                    format => {
+                        let user = UserView {
+                            login: user.name,
+                            name: Some(user.display_name),
+                        };
+                        view::print_one(&user, format);
+                    }
+                }
+
                 Ok(())
             }
             Err(err) => Err(err),
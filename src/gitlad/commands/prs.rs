@@ -1,14 +1,23 @@
 use std::collections::HashMap;
 
-use crate::git::{extract_ticket, AuthDomainConfig, BaseUrlConfig, JiraUrlConfig};
-use crate::gitlab::{get_current_repo_id, Client, Pipeline, PipelineStatus, PullRequest, RepoId};
+use crate::git::{
+    AuthDomainConfig, BaseUrlConfig, JiraUrlConfig, PrsColumnsConfig, TicketPattern,
+    TicketPatternConfig, Tracker, TrackerConfig,
+};
+use crate::gitlab::{
+    get_current_repo_id, Approvals, Client, DetailedMergeStatus, GitLabTracker, MergeStatus,
+    Pipeline, PipelineStatus, PullRequest, PullRequestState, RepoId,
+};
+use crate::tracker::IssueTracker;
+use crate::view::{
+    self, ApprovalsView, CiStatus, Column, Mergeability, Output, PullRequestView, TicketView,
+    UserView,
+};
 use crate::Error;
 
 use clap::ArgMatches;
 use futures_util::{stream, StreamExt};
 use git2::Repository;
-use jira_api::JiraClient;
-use prettytable::{cell, row, Cell, Table};
 
 enum FilterMode {
     ByAuthor(u32),
@@ -27,90 +36,106 @@ impl Prs {
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: PrsColumnsConfig,
+        Conf: TrackerConfig,
     {
-        let repo_id = get_current_repo_id(&repo, config).ok_or(Error::InvalidRepo)?;
-        let client = Client::new(config);
-
-        let filter_mode = Self::filter_mode(args, &client).await;
-
-        let prs = Self::find_all_open_prs(&client, &repo_id, filter_mode).await?;
-        if prs.is_empty() {
-            println!("No open PRs in that repo");
-            return Ok(());
-        }
+        let output = Output::from_args(args, config.prs_columns(), Column::defaults(config))?;
+        let views = Self::collect(args, repo, config).await?;
 
-        Self::print_table_for_prs(&prs, &repo_id, config).await;
+        output.print(&views, "No open PRs in that repo");
 
         Ok(())
     }
 
-    pub async fn print_table_for_prs<Conf>(prs: &[PullRequest], repo_id: &RepoId, config: &Conf)
+    pub async fn collect<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<Vec<PullRequestView>, Error>
     where
         Conf: AuthDomainConfig + Send + Sync,
         Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
     {
-        let mut table = Table::new();
-        table.set_titles(row![
-            "ID",
-            "Author",
-            "Title",
-            "CI",
-            "Likes",
-            "Target",
-            "Last updated",
-            "Jira status"
-        ]);
-
-        let tickets = Self::get_tickets_statuses_for_prs(&prs, config)
-            .await
-            .unwrap_or_default();
-        let pipelines = Self::get_last_pipelines_for_prs(&prs, &repo_id, config).await;
-
-        let na = String::from("N/A");
-
-        for pr in prs {
-            let mut row = row![pr.id, pr.author.display_name, Self::title_for_pr(&pr, 35)];
-
-            let updated = pr.updated - chrono::Utc::now();
-            let updated = chrono_humanize::HumanTime::from(updated);
-
-            match pipelines.get(&pr.id) {
-                Some(pipeline) => match pipeline.status {
-                    PipelineStatus::Pending => row.add_cell(cell!(Fy->"P")),
-                    PipelineStatus::Running => row.add_cell(cell!(Fy->"R")),
-                    PipelineStatus::Success => row.add_cell(cell!(Fg->"S")),
-                    PipelineStatus::Failed => row.add_cell(cell!(Fr->"F")),
-                },
-                None => row.add_cell(cell!("")),
-            };
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
 
-            let upvotes = pr.upvotes;
-            let upvotes_cell = Cell::new(&format!("{}", upvotes));
+        let filter_mode = Self::filter_mode(args, &client).await;
 
-            if upvotes >= 2 {
-                row.add_cell(upvotes_cell.style_spec("Fg"));
-            } else {
-                row.add_cell(upvotes_cell.style_spec("Fr"));
-            }
+        let prs = Self::find_all_open_prs(&client, &repo_id, filter_mode).await?;
 
-            row.add_cell(cell!(pr.target_branch));
-            row.add_cell(cell!(updated));
+        Ok(Self::views_for_prs(&prs, &repo_id, config).await)
+    }
 
-            let status = extract_ticket(&pr.source_branch)
-                .and_then(|ticket| tickets.get(ticket))
-                .unwrap_or(&na);
+    /// Recently updated PRs whose branch or title references the ticket `key`.
+    pub async fn find_for_ticket<Conf>(
+        key: &str,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<Vec<PullRequestView>, Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+    {
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
 
-            row.add_cell(cell!(status));
+        let pattern = config.ticket_pattern();
+        let prs = client
+            .find_recent_prs(&repo_id)
+            .await?
+            .into_iter()
+            .filter(|pr| {
+                pattern.references(&pr.source_branch, key) || pattern.references(&pr.title, key)
+            })
+            .collect::<Vec<_>>();
 
-            table.add_row(row);
-        }
+        Ok(Self::views_for_prs(&prs, &repo_id, config).await)
+    }
 
-        table.printstd();
+    pub async fn views_for_prs<Conf>(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        config: &Conf,
+    ) -> Vec<PullRequestView>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+        Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
+    {
+        let tickets = Self::get_tickets_statuses_for_prs(prs, repo_id, config)
+            .await
+            .unwrap_or_default();
+        let pipelines = Self::get_last_pipelines_for_prs(prs, repo_id, config).await;
+        let approvals = Self::get_approvals_for_prs(prs, repo_id, config).await;
+
+        prs.iter()
+            .map(|pr| {
+                Self::view_for_pr(
+                    pr,
+                    repo_id,
+                    pipelines.get(&pr.id),
+                    approvals.get(&pr.id),
+                    &tickets,
+                    config.ticket_pattern(),
+                )
+            })
+            .collect()
     }
 
     async fn filter_mode(args: &ArgMatches, client: &Client<'_>) -> Option<FilterMode> {
-        match args.get_one::<String>("filter").map(String::as_str) {
+        // `switch` picks from all open PRs and has no filter argument
+        let filter = args.try_get_one::<String>("filter").ok().flatten();
+
+        match filter.map(String::as_str) {
             Some("my") => {
                 let user = client.whoami().await.ok()?;
                 Some(FilterMode::ByAuthor(user.id))
@@ -128,16 +153,6 @@ impl Prs {
         }
     }
 
-    fn title_for_pr(pr: &PullRequest, max_width: usize) -> String {
-        use hyphenation::{Language, Load, Standard};
-        use textwrap::{fill, Options, WordSplitter::Hyphenation};
-
-        let hyphenator = Standard::from_embedded(Language::EnglishUS).unwrap();
-        let options = Options::new(max_width).word_splitter(Hyphenation(hyphenator));
-
-        fill(&pr.title, options)
-    }
-
     async fn get_last_pipelines_for_prs<Conf>(
         prs: &[PullRequest],
         repo_id: &RepoId,
@@ -168,31 +183,182 @@ impl Prs {
         result
     }
 
+    async fn get_approvals_for_prs<Conf>(
+        prs: &[PullRequest],
+        repo_id: &RepoId,
+        config: &Conf,
+    ) -> HashMap<u16, Approvals>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        let client = Client::new(config);
+
+        let approvals = stream::iter(prs.iter().map(|pr| client.get_pr_approvals(pr.id, repo_id)))
+            .buffered(10)
+            .collect::<Vec<_>>()
+            .await;
+
+        prs.iter()
+            .zip(approvals)
+            .filter_map(|(pr, approvals)| Some((pr.id, approvals.ok()?)))
+            .collect()
+    }
+
     async fn get_tickets_statuses_for_prs<Conf>(
         prs: &[PullRequest],
+        repo_id: &RepoId,
         config: &Conf,
     ) -> Option<HashMap<String, String>>
     where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
         Conf: JiraUrlConfig,
+        Conf: TicketPatternConfig,
+        Conf: TrackerConfig,
     {
-        let jira_url = config.jira_url()?;
-        let jira_client = JiraClient::new(jira_url, jira_api::client::AuthType::AccessToken)?;
+        let keys = prs
+            .iter()
+            .filter_map(|pr| {
+                config
+                    .ticket_pattern()
+                    .find_in(&[&pr.source_branch, &pr.title])
+            })
+            .collect::<Vec<_>>();
+        let tickets = keys.iter().map(String::as_str).collect::<Vec<_>>();
+
+        match config.tracker() {
+            Tracker::Jira => crate::jira::statuses_for_tickets(&tickets, config).await,
+            Tracker::Issues => {
+                let tracker = GitLabTracker {
+                    client: Client::new(config),
+                    repo_id,
+                    base_url: config.base_url(),
+                };
+
+                Some(tracker.states(&tickets).await)
+            }
+        }
+    }
+
+    pub fn view_state(pr: &PullRequest) -> view::PullRequestState {
+        match pr.state {
+            PullRequestState::Opened => view::PullRequestState::Open,
+            PullRequestState::Merged => view::PullRequestState::Merged,
+            PullRequestState::Closed | PullRequestState::Locked => view::PullRequestState::Closed,
+        }
+    }
+
+    fn view_for_pr(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        pipeline: Option<&Pipeline>,
+        approvals: Option<&Approvals>,
+        tickets: &HashMap<String, String>,
+        pattern: &TicketPattern,
+    ) -> PullRequestView {
+        let state = Self::view_state(pr);
+
+        let ci = pipeline.and_then(|pipeline| match pipeline.status {
+            PipelineStatus::Pending => Some(CiStatus::Pending),
+            PipelineStatus::Running => Some(CiStatus::Running),
+            PipelineStatus::Success => Some(CiStatus::Passed),
+            PipelineStatus::Failed => Some(CiStatus::Failed),
+            PipelineStatus::Canceled | PipelineStatus::Skipped | PipelineStatus::Other => None,
+        });
+
+        PullRequestView {
+            repository: repo_id.id(),
+            id: pr.id,
+            title: pr.title.clone(),
+            url: pr.url.clone(),
+            author: UserView {
+                login: pr.author.name.clone(),
+                name: Some(pr.author.display_name.clone()),
+            },
+            source: pr.source_branch.clone(),
+            target: pr.target_branch.clone(),
+            created: pr.created,
+            updated: pr.updated,
+            state,
+            ci,
+            approvals: Some(Self::approvals_for_pr(pr, approvals)),
+            mergeability: Self::mergeability_for_pr(pr),
+            ticket: pattern
+                .find_in(&[&pr.source_branch, &pr.title])
+                .map(|key| TicketView::new(&key, tickets)),
+            labels: pr.labels.clone(),
+        }
+    }
+
+    fn approvals_for_pr(pr: &PullRequest, approvals: Option<&Approvals>) -> ApprovalsView {
+        let approvals = match approvals {
+            Some(approvals) => approvals,
+            None => {
+                return ApprovalsView {
+                    approved: pr.upvotes.into(),
+                    reviewers: None,
+                    required: None,
+                }
+            }
+        };
 
-        let mut tickets = prs
+        let mut reviewers = pr
+            .reviewers
             .iter()
-            .filter_map(|pr| extract_ticket(&pr.source_branch))
+            .map(|user| user.id)
+            .chain(
+                approvals
+                    .approved_by
+                    .iter()
+                    .map(|approver| approver.user.id),
+            )
             .collect::<Vec<_>>();
-        tickets.dedup();
+        reviewers.sort_unstable();
+        reviewers.dedup();
 
-        let jql = format!("key in ({})", tickets.join(","));
-        let tickets = crate::jira::pull(&jira_client, &jql).await;
+        let approved = approvals.approved_by.len() as u16;
 
-        Some(
-            tickets
-                .into_iter()
-                .map(|issue| (issue.key, issue.fields.status.name))
-                .collect::<HashMap<_, _>>(),
-        )
+        ApprovalsView {
+            approved,
+            reviewers: Some(reviewers.len() as u16),
+            // `approvals_required` stays 0 for projects with approval rules, what's left counts
+            // for both
+            required: Some(approved + approvals.approvals_left),
+        }
+    }
+
+    fn mergeability_for_pr(pr: &PullRequest) -> Option<Mergeability> {
+        if pr.has_conflicts {
+            return Some(Mergeability::Conflicts);
+        }
+
+        if pr.blocking_discussions_resolved == Some(false) {
+            return Some(Mergeability::BlockedByDiscussions);
+        }
+
+        match pr.detailed_merge_status {
+            Some(DetailedMergeStatus::Mergeable) => Some(Mergeability::Mergeable),
+            Some(DetailedMergeStatus::Conflict) => Some(Mergeability::Conflicts),
+            Some(DetailedMergeStatus::NeedRebase) => Some(Mergeability::Behind),
+            Some(DetailedMergeStatus::CiMustPass) | Some(DetailedMergeStatus::CiStillRunning) => {
+                Some(Mergeability::BlockedByChecks)
+            }
+            Some(DetailedMergeStatus::DiscussionsNotResolved) => {
+                Some(Mergeability::BlockedByDiscussions)
+            }
+            Some(DetailedMergeStatus::DraftStatus) => Some(Mergeability::Draft),
+            Some(DetailedMergeStatus::Checking) | Some(DetailedMergeStatus::Unchecked) => None,
+            Some(_) => Some(Mergeability::Blocked),
+            // older GitLab versions only report `merge_status`
+            None => match pr.merge_status? {
+                MergeStatus::CanBeMerged => Some(Mergeability::Mergeable),
+                MergeStatus::CannotBeMerged | MergeStatus::CannotBeMergedRecheck => {
+                    Some(Mergeability::Conflicts)
+                }
+                MergeStatus::Checking | MergeStatus::Unchecked | MergeStatus::Unknown => None,
+            },
+        }
     }
 
     async fn find_all_open_prs(
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+
+use crate::git::{AuthDomainConfig, BaseUrlConfig};
+use crate::gitlab::{get_current_repo_id, Client, PipelineStatus, PullRequest, RepoId};
+use crate::view::{self, Format, PullRequestHistory, PullRequestStats};
+use crate::Error;
+
+use clap::ArgMatches;
+use futures_util::{stream, StreamExt};
+use git2::Repository;
+
+pub struct Stats;
+
+impl Stats {
+    pub async fn handle<Conf>(
+        args: &ArgMatches,
+        repo: &Repository,
+        config: &Conf,
+    ) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        match args.subcommand() {
+            Some(("prs", args)) => Self::prs(args, repo, config).await,
+            _ => unreachable!("subcommand is required"),
+        }
+    }
+
+    async fn prs<Conf>(args: &ArgMatches, repo: &Repository, config: &Conf) -> Result<(), Error>
+    where
+        Conf: AuthDomainConfig + Send + Sync,
+        Conf: BaseUrlConfig,
+    {
+        let since = *args.get_one::<DateTime<Utc>>("since").expect("default");
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        let repo_id = get_current_repo_id(repo, config).ok_or(Error::InvalidRepo)?;
+        let client = Client::new(config);
+
+        let mut prs = Self::find_closed_prs(&client, &repo_id, "merged", since).await?;
+        prs.extend(Self::find_closed_prs(&client, &repo_id, "closed", since).await?);
+
+        let history = stream::iter(
+            prs.iter()
+                .map(|pr| Self::history_for_pr(pr, &repo_id, &client)),
+        )
+        .buffered(10)
+        .collect::<Vec<_>>()
+        .await;
+
+        let stats = PullRequestStats::new(repo_id.id(), since, &history);
+
+        view::print_stats(&stats, format);
+
+        Ok(())
+    }
+
+    async fn find_closed_prs(
+        client: &Client<'_>,
+        repo_id: &RepoId,
+        state: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<PullRequest>, Error> {
+        let mut result = vec![];
+        let mut page = 1;
+
+        loop {
+            let prs = client.find_closed_prs(repo_id, state, &since, page).await?;
+
+            if prs.is_empty() {
+                return Ok(result);
+            }
+
+            // `updated_after` also matches MRs that were only commented on recently
+            result.extend(prs.into_iter().filter(|pr| {
+                pr.merged_at
+                    .or(pr.closed_at)
+                    .is_some_and(|closed| closed >= since)
+            }));
+
+            page += 1;
+        }
+    }
+
+    async fn history_for_pr(
+        pr: &PullRequest,
+        repo_id: &RepoId,
+        client: &Client<'_>,
+    ) -> PullRequestHistory {
+        // the first page is enough to find the first review
+        let notes = client
+            .get_pr_notes(pr.id, repo_id)
+            .await
+            .unwrap_or_default();
+
+        let notes = notes
+            .iter()
+            .filter(|note| !note.system && note.author.id != pr.author.id)
+            .collect::<Vec<_>>();
+
+        let mut reviewers = notes
+            .iter()
+            .map(|note| note.author.name.clone())
+            .collect::<Vec<_>>();
+
+        if let Ok(approvals) = client.get_pr_approvals(pr.id, repo_id).await {
+            reviewers.extend(
+                approvals
+                    .approved_by
+                    .into_iter()
+                    .map(|approver| approver.user.name),
+            );
+        }
+
+        reviewers.sort_unstable();
+        reviewers.dedup();
+
+        // the list endpoint doesn't include the diff stats
+        let size = client
+            .get_pr_by_id(pr.id, repo_id)
+            .await
+            .ok()
+            .and_then(|pr| pr.changes_count?.trim_end_matches('+').parse().ok());
+
+        let (ci_runs, ci_failures) = match client.get_pr_pipelines(pr.id, repo_id).await {
+            Ok(pipelines) => {
+                let finished = pipelines
+                    .iter()
+                    .filter(|pipeline| {
+                        matches!(
+                            pipeline.status,
+                            PipelineStatus::Success | PipelineStatus::Failed
+                        )
+                    })
+                    .count();
+
+                let failures = pipelines
+                    .iter()
+                    .filter(|pipeline| matches!(pipeline.status, PipelineStatus::Failed))
+                    .count();
+
+                (finished as u32, failures as u32)
+            }
+            Err(_) => (0, 0),
+        };
+
+        PullRequestHistory {
+            created: pr.created,
+            merged: pr.merged_at,
+            first_review: notes.first().map(|note| note.created_at),
+            size,
+            reviewers,
+            ci_runs,
+            ci_failures,
+        }
+    }
+}
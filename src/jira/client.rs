@@ -0,0 +1,290 @@
+use chipp_http::{Error, HttpClient, HttpMethod, Request, Response};
+use serde::Serialize;
+use url::Url;
+
+use crate::git::JiraAuth;
+use crate::Authenticator;
+
+use super::issue::{CreatedIssue, SearchResults};
+use super::transition::Transitions;
+use super::{Issue, Transition, User};
+
+pub struct Client<'a> {
+    inner: HttpClient<Authenticator<'a>>,
+}
+
+impl Client<'_> {
+    pub fn new(jira_url: &Url, auth: JiraAuth) -> Client<'_> {
+        let auth_domain = jira_url.host_str().unwrap_or_default();
+
+        let authenticator = match auth {
+            JiraAuth::Token => Authenticator::token(auth_domain, "access_token"),
+            JiraAuth::Basic => Authenticator::basic_auth(auth_domain),
+        };
+
+        let inner = HttpClient::new(api_url(jira_url))
+            .unwrap()
+            .with_interceptor(authenticator);
+
+        Client { inner }
+    }
+}
+
+impl Client<'_> {
+    pub async fn get_issue(&self, key: &str) -> Result<Issue, Error> {
+        // `names` maps custom field ids to their names, it's the only way to find the sprint
+        self.inner
+            .get_with_params(vec!["issue", key], &[("expand", "names")])
+            .await
+    }
+
+    pub async fn search_issues(
+        &self,
+        jql: &str,
+        fields: &str,
+        start_at: u32,
+    ) -> Result<SearchResults, Error> {
+        let start_at = start_at.to_string();
+
+        self.inner
+            .get_with_params(
+                vec!["search"],
+                &[
+                    ("jql", jql),
+                    ("fields", fields),
+                    ("startAt", &start_at),
+                    ("maxResults", "500"),
+                ],
+            )
+            .await
+    }
+
+    pub async fn create_issue(
+        &self,
+        project: &str,
+        issue_type: &str,
+        summary: &str,
+        description: Option<&str>,
+    ) -> Result<CreatedIssue, Error> {
+        #[derive(Serialize)]
+        struct CreateBody<'a> {
+            fields: CreateFields<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct CreateFields<'a> {
+            project: Key<'a>,
+            issuetype: Name<'a>,
+            summary: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'a str>,
+        }
+
+        #[derive(Serialize)]
+        struct Key<'a> {
+            key: &'a str,
+        }
+
+        #[derive(Serialize)]
+        struct Name<'a> {
+            name: &'a str,
+        }
+
+        let mut request = self.inner.new_request(vec!["issue"]);
+        request.set_json_body(&CreateBody {
+            fields: CreateFields {
+                project: Key { key: project },
+                issuetype: Name { name: issue_type },
+                summary,
+                description,
+            },
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner
+            .perform_request(request, chipp_http::json::parse_json)
+            .await
+    }
+
+    pub async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>, Error> {
+        let transitions: Transitions = self.inner.get(vec!["issue", key, "transitions"]).await?;
+        Ok(transitions.transitions)
+    }
+
+    pub async fn transition_issue(&self, key: &str, transition_id: &str) -> Result<(), Error> {
+        #[derive(Serialize)]
+        struct TransitionBody<'a> {
+            transition: TransitionId<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct TransitionId<'a> {
+            id: &'a str,
+        }
+
+        let mut request = self.inner.new_request(vec!["issue", key, "transitions"]);
+        request.set_json_body(&TransitionBody {
+            transition: TransitionId { id: transition_id },
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner.perform_request(request, parse_empty).await
+    }
+
+    pub async fn whoami(&self) -> Result<User, Error> {
+        self.inner.get(vec!["myself"]).await
+    }
+
+    pub async fn assign_issue(&self, key: &str, user: &User) -> Result<(), Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AssigneeBody<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            name: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            account_id: Option<&'a str>,
+        }
+
+        let mut request = self.inner.new_request(vec!["issue", key, "assignee"]);
+        request.set_json_body(&AssigneeBody {
+            name: user.name.as_deref(),
+            account_id: user.account_id.as_deref(),
+        });
+        request.set_method(HttpMethod::Put);
+
+        self.inner.perform_request(request, parse_empty).await
+    }
+
+    /// Adds a link to `url`, or updates the existing one since `url` is also the link's id.
+    pub async fn add_remote_link(
+        &self,
+        key: &str,
+        url: &Url,
+        title: &str,
+        resolved: bool,
+    ) -> Result<(), Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RemoteLinkBody<'a> {
+            global_id: &'a str,
+            object: RemoteObject<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct RemoteObject<'a> {
+            url: &'a str,
+            title: &'a str,
+            status: RemoteStatus,
+        }
+
+        #[derive(Serialize)]
+        struct RemoteStatus {
+            resolved: bool,
+        }
+
+        let mut request = self.inner.new_request(vec!["issue", key, "remotelink"]);
+        request.set_json_body(&RemoteLinkBody {
+            global_id: url.as_str(),
+            object: RemoteObject {
+                url: url.as_str(),
+                title,
+                status: RemoteStatus { resolved },
+            },
+        });
+        request.set_method(HttpMethod::Post);
+
+        self.inner.perform_request(request, parse_empty).await
+    }
+
+    pub async fn add_comment(&self, key: &str, body: &str) -> Result<(), Error> {
+        #[derive(Serialize)]
+        struct CommentBody<'a> {
+            body: &'a str,
+        }
+
+        let mut request = self.inner.new_request(vec!["issue", key, "comment"]);
+        request.set_json_body(&CommentBody { body });
+        request.set_method(HttpMethod::Post);
+
+        self.inner.perform_request(request, parse_empty).await
+    }
+}
+
+/// The REST API under `jira_url`, keeping its path for instances served under a context
+/// path like `https://example.com/jira`.
+fn api_url(jira_url: &Url) -> Url {
+    let mut url = jira_url.clone();
+
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+
+    url.join("rest/api/2/").unwrap()
+}
+
+/// Parses the responses of write calls whose body gitext doesn't need, like 204 No Content
+/// after a transition or the created link or comment.
+// chipp_http's own parsers return its error unboxed too
+#[allow(clippy::result_large_err)]
+fn parse_empty(request: Request, response: Response) -> Result<(), Error> {
+    if (200..300).contains(&response.status_code) {
+        Ok(())
+    } else {
+        Err((request, response).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chipp_http::ErrorKind;
+
+    fn transition_request() -> Request {
+        let url = "https://jira.example.com/rest/api/2/issue/IOS-1/transitions";
+        Request::new(Url::parse(url).unwrap())
+    }
+
+    fn response(status_code: u32) -> Response {
+        Response {
+            status_code,
+            body: vec![],
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn api_url_keeps_context_path() {
+        let api_url = |jira_url: &str| api_url(&Url::parse(jira_url).unwrap()).to_string();
+
+        assert_eq!(
+            api_url("https://jira.example.com"),
+            "https://jira.example.com/rest/api/2/"
+        );
+        assert_eq!(
+            api_url("https://example.com/jira"),
+            "https://example.com/jira/rest/api/2/"
+        );
+        assert_eq!(
+            api_url("https://example.com/jira/"),
+            "https://example.com/jira/rest/api/2/"
+        );
+    }
+
+    #[test]
+    fn accepted_transition() {
+        assert!(parse_empty(transition_request(), response(204)).is_ok());
+    }
+
+    #[test]
+    fn rejected_transition() {
+        for status_code in [400, 401, 403, 404] {
+            let err = parse_empty(transition_request(), response(status_code)).unwrap_err();
+
+            match err.kind {
+                ErrorKind::HttpError(response) => assert_eq!(response.status_code, status_code),
+                _ => panic!("expected an HTTP error for {}", status_code),
+            }
+        }
+    }
+}
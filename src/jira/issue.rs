@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+pub struct Issue {
+    pub key: String,
+    pub fields: Fields,
+
+    /// Names of the fields, only returned with `expand=names`
+    #[serde(default)]
+    pub names: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatedIssue {
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+pub struct SearchResults {
+    pub total: u32,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Deserialize)]
+pub struct Fields {
+    pub summary: String,
+    pub status: Status,
+    pub assignee: Option<User>,
+    pub priority: Option<Priority>,
+    pub description: Option<String>,
+
+    #[serde(flatten)]
+    pub custom: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Status {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub display_name: String,
+
+    /// Jira Server identifies users by name
+    pub name: Option<String>,
+    /// Jira Cloud identifies users by account id
+    pub account_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Priority {
+    pub name: String,
+}
+
+impl Issue {
+    /// Name of the active sprint, or the latest one if none of them is active.
+    ///
+    /// Sprint is a custom field with a different id on every instance. Jira Cloud
+    /// returns sprints as objects, Jira Server as strings like
+    /// `com.atlassian.greenhopper.service.sprint.Sprint@1[id=1,state=ACTIVE,name=Sprint 1,...]`.
+    pub fn sprint(&self) -> Option<String> {
+        let (id, _) = self.names.iter().find(|(_, name)| *name == "Sprint")?;
+        let sprints = self.fields.custom.get(id)?.as_array()?;
+
+        let sprints = sprints
+            .iter()
+            .filter_map(|sprint| match sprint {
+                Value::Object(sprint) => Some((
+                    sprint.get("name")?.as_str()?.to_string(),
+                    sprint.get("state")?.as_str()?.to_lowercase(),
+                )),
+                Value::String(sprint) => Some((
+                    sprint_attribute(sprint, "name")?.to_string(),
+                    sprint_attribute(sprint, "state")?.to_lowercase(),
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        sprints
+            .iter()
+            .find(|(_, state)| state == "active")
+            .or_else(|| sprints.last())
+            .map(|(name, _)| name.clone())
+    }
+}
+
+fn sprint_attribute<'s>(sprint: &'s str, attribute: &str) -> Option<&'s str> {
+    let start = sprint.find('[')? + 1;
+    let end = sprint.rfind(']')?;
+
+    sprint[start..end]
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == attribute)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!({
+            "key": "IOS-1212",
+            "names": {
+                "summary": "Summary",
+                "customfield_10020": "Sprint"
+            },
+            "fields": {
+                "summary": "Test title",
+                "status": { "name": "In Progress" },
+                "assignee": { "displayName": "Vasili Pupkin" },
+                "priority": { "name": "High" },
+                "description": "h1. magic description",
+                "customfield_10020": [
+                    { "id": 1, "name": "Sprint 41", "state": "closed" },
+                    { "id": 2, "name": "Sprint 42", "state": "active" }
+                ]
+            }
+        });
+
+        let issue: Issue = serde_json::from_value(json).unwrap();
+
+        assert_eq!(issue.key, "IOS-1212");
+        assert_eq!(issue.fields.summary, "Test title");
+        assert_eq!(issue.fields.status.name, "In Progress");
+        assert_eq!(
+            issue.fields.assignee.as_ref().unwrap().display_name,
+            "Vasili Pupkin"
+        );
+        assert_eq!(issue.fields.priority.as_ref().unwrap().name, "High");
+        assert_eq!(issue.sprint().as_deref(), Some("Sprint 42"));
+    }
+
+    #[test]
+    fn server_sprint() {
+        let json = serde_json::json!({
+            "key": "IOS-1212",
+            "names": { "customfield_10004": "Sprint" },
+            "fields": {
+                "summary": "Test title",
+                "status": { "name": "Open" },
+                "assignee": null,
+                "priority": null,
+                "description": null,
+                "customfield_10004": [
+                    "com.atlassian.greenhopper.service.sprint.Sprint@5e6b4[id=7,rapidViewId=1,state=CLOSED,name=Sprint 7,startDate=2024-10-01T10:00:00.000Z]"
+                ]
+            }
+        });
+
+        let issue: Issue = serde_json::from_value(json).unwrap();
+
+        assert!(issue.fields.assignee.is_none());
+        assert_eq!(issue.sprint().as_deref(), Some("Sprint 7"));
+    }
+}
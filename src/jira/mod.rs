@@ -0,0 +1,193 @@
+mod client;
+mod issue;
+mod tracker;
+mod transition;
+
+pub use client::Client;
+pub use issue::{Issue, User};
+pub use tracker::JiraTracker;
+pub use transition::Transition;
+
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::git::{JiraLinkConfig, JiraPrLink, JiraUrlConfig};
+use crate::Error;
+
+pub fn browse_url(jira_url: &Url, key: &str) -> Url {
+    let mut url = jira_url.clone();
+
+    {
+        let mut segments = url.path_segments_mut().unwrap();
+        segments.pop_if_empty().push("browse").push(key);
+    }
+
+    url
+}
+
+/// Fetches every issue matching `jql`, a page at a time.
+pub async fn pull(
+    client: &Client<'_>,
+    jql: &str,
+    fields: &str,
+) -> Result<Vec<Issue>, chipp_http::Error> {
+    let mut issues = vec![];
+
+    loop {
+        let mut response = client
+            .search_issues(jql, fields, issues.len() as u32)
+            .await?;
+
+        let is_last_page = response.issues.is_empty()
+            || issues.len() + response.issues.len() >= response.total as usize;
+
+        issues.append(&mut response.issues);
+
+        if is_last_page {
+            return Ok(issues);
+        }
+    }
+}
+
+/// Statuses of `tickets`, or `None` when Jira isn't configured or can't be reached.
+pub async fn statuses_for_tickets<Conf>(
+    tickets: &[&str],
+    config: &Conf,
+) -> Option<HashMap<String, String>>
+where
+    Conf: JiraUrlConfig,
+{
+    let jira_url = config.jira_url()?;
+
+    let mut tickets = tickets.to_vec();
+    tickets.sort_unstable();
+    tickets.dedup();
+
+    if tickets.is_empty() {
+        return Some(HashMap::new());
+    }
+
+    let client = Client::new(jira_url, config.jira_auth());
+    let jql = format!("key in ({})", tickets.join(","));
+
+    // the tables are still useful without ticket statuses
+    let tickets = match pull(&client, &jql, "summary,status").await {
+        Ok(tickets) => tickets,
+        Err(err) => {
+            eprintln!("warning: can't get ticket statuses from Jira: {err}");
+            eprintln!("hint: run `git ticket auth` to check your Jira credentials");
+            return None;
+        }
+    };
+
+    Some(
+        tickets
+            .into_iter()
+            .map(|issue| (issue.key, issue.fields.status.name))
+            .collect::<HashMap<_, _>>(),
+    )
+}
+
+/// Moves `key` through the transition called `name` or to the status called `name`.
+pub async fn transition_ticket(
+    client: &Client<'_>,
+    key: &str,
+    name: &str,
+) -> Result<Transition, Error> {
+    let transition = client
+        .get_transitions(key)
+        .await?
+        .into_iter()
+        .find(|transition| transition.matches(name))
+        .ok_or_else(|| Error::UnknownTransition(key.to_string(), name.to_string()))?;
+
+    client.transition_issue(key, &transition.id).await?;
+
+    Ok(transition)
+}
+
+/// Moves the ticket `key` to `status` after a PR lifecycle event.
+///
+/// The PR is already created or merged at this point, so failures are only reported.
+pub async fn run_transition_hook<Conf>(key: Option<&str>, status: Option<&str>, config: &Conf)
+where
+    Conf: JiraUrlConfig,
+{
+    let (status, jira_url, key) = match (status, config.jira_url(), key) {
+        (Some(status), Some(jira_url), Some(key)) => (status, jira_url, key),
+        _ => return,
+    };
+
+    let client = Client::new(jira_url, config.jira_auth());
+
+    match transition_ticket(&client, key, status).await {
+        Ok(transition) => println!("Moved {key} to {}", transition.to.name),
+        Err(err) => eprintln!("warning: can't move {key} to `{status}`: {err}"),
+    }
+}
+
+/// A PR to mention on its ticket.
+pub struct PrLink<'a> {
+    /// `#12` or `!12`, depending on the provider
+    pub id: String,
+    pub title: &'a str,
+    pub url: &'a Url,
+    pub state: &'a str,
+}
+
+/// Links `pr` to the ticket `key` with a remote link or a comment, as configured.
+///
+/// Like transitions, failures are only reported.
+pub async fn run_link_hook<Conf>(key: Option<&str>, pr: &PrLink<'_>, config: &Conf)
+where
+    Conf: JiraUrlConfig,
+    Conf: JiraLinkConfig,
+{
+    let (link, jira_url, key) = match (config.jira_pr_link(), config.jira_url(), key) {
+        (Some(link), Some(jira_url), Some(key)) => (link, jira_url, key),
+        _ => return,
+    };
+
+    let client = Client::new(jira_url, config.jira_auth());
+    let title = format!("PR {}: {} [{}]", pr.id, pr.title, pr.state);
+
+    let result = match link {
+        JiraPrLink::RemoteLink => {
+            let resolved = pr.state != "open";
+            client.add_remote_link(key, pr.url, &title, resolved).await
+        }
+        JiraPrLink::Comment => {
+            let body = format!("{title}\n{}", pr.url);
+            client.add_comment(key, &body).await
+        }
+    };
+
+    match result {
+        Ok(()) => println!("Linked {} to {key}", pr.id),
+        Err(err) => eprintln!("warning: can't link {} to {key}: {err}", pr.id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browse_url_keeps_context_path() {
+        let browse = |jira_url: &str| browse_url(&Url::parse(jira_url).unwrap(), "IOS-1");
+
+        assert_eq!(
+            browse("https://jira.example.com").as_str(),
+            "https://jira.example.com/browse/IOS-1"
+        );
+        assert_eq!(
+            browse("https://example.com/jira").as_str(),
+            "https://example.com/jira/browse/IOS-1"
+        );
+        assert_eq!(
+            browse("https://example.com/jira/").as_str(),
+            "https://example.com/jira/browse/IOS-1"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::tracker::{IssueTracker, TrackedIssue};
+use crate::Error;
+
+use super::{browse_url, pull, transition_ticket, Client, Issue};
+
+pub struct JiraTracker<'a> {
+    pub client: Client<'a>,
+    pub jira_url: &'a Url,
+    /// Query for `list`
+    pub jql: &'a str,
+    /// Project for new issues
+    pub project: Option<&'a str>,
+    pub issue_type: &'a str,
+    /// Status `close` moves issues to
+    pub close_status: &'a str,
+}
+
+impl JiraTracker<'_> {
+    fn tracked(&self, issue: Issue) -> TrackedIssue {
+        TrackedIssue {
+            url: browse_url(self.jira_url, &issue.key),
+            key: issue.key,
+            title: issue.fields.summary,
+            state: issue.fields.status.name,
+            assignee: issue.fields.assignee.map(|user| user.display_name),
+            description: issue.fields.description,
+        }
+    }
+}
+
+impl IssueTracker for JiraTracker<'_> {
+    async fn list(&self) -> Result<Vec<TrackedIssue>, Error> {
+        let issues = pull(&self.client, self.jql, "summary,status,assignee").await?;
+        Ok(issues
+            .into_iter()
+            .map(|issue| self.tracked(issue))
+            .collect())
+    }
+
+    async fn get(&self, key: &str) -> Result<TrackedIssue, Error> {
+        let issue = self.client.get_issue(key).await?;
+        Ok(self.tracked(issue))
+    }
+
+    async fn create(&self, title: &str, description: Option<&str>) -> Result<TrackedIssue, Error> {
+        let project = self.project.ok_or(Error::NoJiraProject)?;
+
+        let created = self
+            .client
+            .create_issue(project, self.issue_type, title, description)
+            .await?;
+
+        self.get(&created.key).await
+    }
+
+    async fn close(&self, key: &str) -> Result<TrackedIssue, Error> {
+        transition_ticket(&self.client, key, self.close_status).await?;
+        self.get(key).await
+    }
+
+    async fn states(&self, keys: &[&str]) -> HashMap<String, String> {
+        if keys.is_empty() {
+            return HashMap::new();
+        }
+
+        let jql = format!("key in ({})", keys.join(","));
+
+        match pull(&self.client, &jql, "summary,status").await {
+            Ok(issues) => issues
+                .into_iter()
+                .map(|issue| (issue.key, issue.fields.status.name))
+                .collect(),
+            Err(err) => {
+                eprintln!("warning: can't get ticket statuses from Jira: {err}");
+                HashMap::new()
+            }
+        }
+    }
+
+    fn url(&self, key: &str) -> Url {
+        browse_url(self.jira_url, key)
+    }
+}
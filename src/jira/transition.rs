@@ -0,0 +1,56 @@
+use serde::Deserialize;
+
+use super::issue::Status;
+
+#[derive(Deserialize)]
+pub struct Transitions {
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Deserialize)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    pub to: Status,
+}
+
+impl Transition {
+    /// Transitions are usually named after their target status, but not always,
+    /// e.g. "Start Progress" moves a ticket to "In Progress". Both names match.
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.to.name.eq_ignore_ascii_case(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let json = serde_json::json!({
+            "expand": "transitions",
+            "transitions": [
+                {
+                    "id": "11",
+                    "name": "Start Progress",
+                    "to": { "id": "3", "name": "In Progress" }
+                },
+                {
+                    "id": "31",
+                    "name": "Done",
+                    "to": { "id": "10001", "name": "Done" }
+                }
+            ]
+        });
+
+        let transitions: Transitions = serde_json::from_value(json).unwrap();
+        let transitions = transitions.transitions;
+
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].id, "11");
+        assert!(transitions[0].matches("in progress"));
+        assert!(transitions[0].matches("Start progress"));
+        assert!(!transitions[0].matches("Done"));
+    }
+}
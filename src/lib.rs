@@ -1,8 +1,20 @@
 mod commands {
+    pub mod credential;
+    pub mod dashboard;
+    pub mod hooks;
+    pub mod issue;
     pub mod ticket;
+    pub mod tickets;
+    pub mod worktrees;
 }
 
+pub use commands::credential::Credential;
+pub use commands::dashboard::Dashboard;
+pub use commands::hooks::Hooks;
+pub use commands::issue::Issue;
 pub use commands::ticket::Ticket;
+pub use commands::tickets::Tickets;
+pub use commands::worktrees::Worktrees;
 
 mod auth;
 mod cli;
@@ -10,6 +22,8 @@ mod error;
 mod git;
 mod jira;
 mod shellquote;
+mod tracker;
+mod view;
 
 mod bitbucket;
 mod gitbucket;
@@ -30,11 +44,13 @@ use git2::{ErrorClass as GitErrorClass, ErrorCode as GitErrorCode};
 use url::Url;
 
 use cli::cli;
+use cli::CloneTarget;
 use error::Error;
 use git::{
-    exec_git_cmd, get_aliases_from_config, get_config, get_repo, set_provider, Config, ConfigError,
-    Provider::*,
+    exec_git_cmd, get_aliases_from_config, get_config, get_configured_hosts, get_host_config,
+    get_repo, set_provider, Config, ConfigError, Provider, Provider::*, Tracker,
 };
+use view::Format;
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -44,6 +60,23 @@ pub async fn handle(args: std::env::Args) -> Result<()> {
 
     let mut args = args.collect::<Vec<_>>();
 
+    // git runs credential helpers wherever it is, even in repositories gitext can't handle
+    if args.get(1).map(String::as_str) == Some("credential") {
+        // `git credential fill`, `approve` and `reject` are git's own
+        let matches = match cli(GitHub).try_get_matches_from(&args) {
+            Ok(matches) => matches,
+            Err(_) => return exec_git_cmd(&args[1..], None),
+        };
+        let (_, args) = matches.subcommand().unwrap();
+
+        return Credential::handle(args, &path);
+    }
+
+    // hooks run on every commit, a config gitext can't read mustn't abort them
+    if args.get(1).map(String::as_str) == Some("hooks") {
+        return handle_hooks(&args, &path);
+    }
+
     let (repo, config) = match repo_and_config(&path) {
         Ok(tuple) => tuple,
         Err(Error::Git(err))
@@ -56,11 +89,22 @@ pub async fn handle(args: std::env::Args) -> Result<()> {
                 }
             };
 
-            if let Some(("clone", args)) = matches.subcommand() {
-                let config = Config::default();
-                return handle_github_clone(args, &config, &path).await;
-            } else {
-                return exec_git_cmd(&args[1..], None);
+            match matches.subcommand() {
+                Some(("clone", clone_args)) => {
+                    let config = match clone_config(clone_args)? {
+                        Some(config) => config,
+                        // a url of a host gitext doesn't know is git's to clone
+                        None => return exec_git_cmd(&args[1..], None),
+                    };
+                    return handle_clone(clone_args, &config, &path).await;
+                }
+                Some(("prs", args)) if args.get_flag("all") => {
+                    return Dashboard::handle(args, None, &path).await;
+                }
+                Some(("auth", args)) if args.subcommand_matches("setup-git").is_some() => {
+                    return Credential::setup_git(None);
+                }
+                _ => return exec_git_cmd(&args[1..], None),
             }
         }
         Err(_) => {
@@ -87,6 +131,14 @@ pub async fn handle(args: std::env::Args) -> Result<()> {
 
     let (command, sub_matches) = matches.subcommand().unwrap();
 
+    if command == "prs" && sub_matches.get_flag("all") {
+        return Dashboard::handle(sub_matches, Some(&repo), &path).await;
+    }
+
+    if command == "auth" && sub_matches.subcommand_matches("setup-git").is_some() {
+        return Credential::setup_git(Some(&config));
+    }
+
     let is_handled = match config.provider {
         BitBucket => handle_bitbucket(&command, sub_matches, &repo, &config, &path).await?,
         GitLab => handle_gitlab(&command, sub_matches, &repo, &config, &path).await?,
@@ -95,7 +147,12 @@ pub async fn handle(args: std::env::Args) -> Result<()> {
 
     if !is_handled {
         match command.as_ref() {
-            "ticket" => Ticket::handle(repo, config)?,
+            "issue" => Issue::handle(sub_matches, &repo, &config).await?,
+            "ticket" if config.tracker == Tracker::Issues => {
+                Issue::handle(sub_matches, &repo, &config).await?
+            }
+            "ticket" => Ticket::handle(sub_matches, &repo, &config).await?,
+            "tickets" => Tickets::handle(sub_matches, &repo, &config).await?,
             _ => exec_git_cmd(&args[1..], Some(&repo))?,
         }
     }
@@ -103,6 +160,26 @@ pub async fn handle(args: std::env::Args) -> Result<()> {
     Ok(())
 }
 
+fn handle_hooks(args: &[String], path: &Path) -> Result<()> {
+    let matches = match cli(GitHub).try_get_matches_from(args) {
+        Ok(matches) => matches,
+        Err(_) => return exec_git_cmd(&args[1..], None),
+    };
+    let (_, sub_matches) = matches.subcommand().unwrap();
+
+    let repo = get_repo(path)?;
+
+    // GitHub repositories are handled without a provider, see `repo_and_config`
+    let has_provider = repo
+        .config()
+        .is_ok_and(|config| config.get_string("gitext.provider").is_ok());
+    if !has_provider && is_github_repo(&repo) == Some(true) {
+        let _ = set_provider(&repo, GitHub);
+    }
+
+    Hooks::handle(sub_matches, &repo)
+}
+
 fn repo_and_config(path: &Path) -> Result<(Repository, Config)> {
     let repo = get_repo(&path)?;
 
@@ -160,14 +237,15 @@ async fn handle_bitbucket(
     config: &Config,
     path: &Path,
 ) -> Result<bool> {
-    use gitbucket::{Auth, Browse, Create, Pr, Prs, Switch};
+    use gitbucket::{Auth, Browse, Create, Pr, Prs, Stats, Switch};
 
     match command {
-        "auth" => Auth::handle(config).await?,
+        "auth" => Auth::handle(config, format(args)).await?,
         "browse" => Browse::handle(args, repo, config, &path)?,
         "create" => Create::handle(args, repo, config).await?,
         "pr" => Pr::handle(args, repo, config).await?,
         "prs" => Prs::handle(args, repo, config).await?,
+        "stats" => Stats::handle(args, repo, config).await?,
         "switch" => {
             if !Switch::handle(args, repo, config).await? {
                 return Ok(false);
@@ -186,14 +264,15 @@ async fn handle_gitlab(
     config: &Config,
     path: &Path,
 ) -> Result<bool> {
-    use gitlad::{Auth, Browse, Pr, Prs, Switch};
+    use gitlad::{Auth, Browse, Pr, Prs, Stats, Switch};
 
     match command {
-        "auth" => Auth::handle(config).await?,
+        "auth" => Auth::handle(config, format(args)).await?,
         "browse" => Browse::handle(args, repo, config, &path)?,
         "create" => unimplemented!("to be implemented"),
         "pr" => Pr::handle(args, repo, config).await?,
         "prs" => Prs::handle(args, repo, config).await?,
+        "stats" => Stats::handle(args, repo, config).await?,
         "switch" => {
             if !Switch::handle(args, repo, config).await? {
                 return Ok(false);
@@ -212,14 +291,15 @@ async fn handle_github(
     config: &Config,
     path: &Path,
 ) -> Result<bool> {
-    use gighub::{Auth, Browse, Create, Pr, Prs, Switch};
+    use gighub::{Auth, Browse, Create, Pr, Prs, Stats, Switch};
 
     match command {
-        "auth" => Auth::handle(config).await?,
+        "auth" => Auth::handle(config, format(args)).await?,
         "browse" => Browse::handle(args, repo, config, &path)?,
         "create" => Create::handle(args, repo, config).await?,
         "pr" => Pr::handle(args, repo, config).await?,
         "prs" => Prs::handle(args, repo, config).await?,
+        "stats" => Stats::handle(args, repo, config).await?,
         "switch" => {
             if !Switch::handle(args, repo, config).await? {
                 return Ok(false);
@@ -231,6 +311,75 @@ async fn handle_github(
     Ok(true)
 }
 
-async fn handle_github_clone(args: &ArgMatches, config: &Config, path: &Path) -> Result<()> {
-    gighub::Clone::handle(args, config, path).await
+fn format(args: &ArgMatches) -> Format {
+    args.get_one::<Format>("format")
+        .copied()
+        .unwrap_or_default()
+}
+
+async fn handle_clone(args: &ArgMatches, config: &Config, path: &Path) -> Result<()> {
+    match config.provider {
+        BitBucket => gitbucket::Clone::handle(args, config, path).await,
+        GitLab => gitlad::Clone::handle(args, config, path).await,
+        GitHub => gighub::Clone::handle(args, config, path).await,
+    }
+}
+
+/// The config of the host `clone` clones from, `--host` or the host of the url, `None` for
+/// urls of hosts without a `gitext.host.<host>` config when neither `--provider` nor
+/// `--host` is given, git clones those as they are.
+fn clone_config(args: &ArgMatches) -> Result<Option<Config>> {
+    let target: &CloneTarget = args.get_one("repo").unwrap();
+
+    let provider = match args.get_one::<String>("provider") {
+        Some(provider) => Some(provider.parse::<Provider>()?),
+        None => None,
+    };
+
+    let explicit_host = args.get_one::<String>("host").map(String::as_str);
+    let global = GitConfig::open_default()?;
+
+    let host = match explicit_host.or(target.host()) {
+        Some(host) => host,
+        None => {
+            return match provider {
+                None | Some(GitHub) => Ok(Some(Config::default())),
+                Some(provider) => the_only_host_config(&global, provider).map(Some),
+            }
+        }
+    };
+
+    match get_host_config(&global, host) {
+        Ok(config) => Ok(Some(Config {
+            provider: provider.unwrap_or(config.provider),
+            ..config
+        })),
+        Err(ConfigError::ProviderNotSpecified) => match provider {
+            Some(provider) => Ok(Some(Config {
+                provider,
+                base_url: Url::parse(&format!("https://{host}"))
+                    .map_err(|_| ConfigError::InvalidBaseUrl(host.to_string()))?,
+                auth_domain: host.to_string(),
+                ..Config::default()
+            })),
+            None if explicit_host.is_none() => Ok(None),
+            None if host == "github.com" => Ok(Some(Config::default())),
+            None => Err(Error::UnknownHost(host.to_string())),
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The config of the single host configured for `provider`.
+fn the_only_host_config(global: &GitConfig, provider: Provider) -> Result<Config> {
+    let hosts = get_configured_hosts(global);
+    let mut configs = hosts
+        .iter()
+        .filter_map(|host| get_host_config(global, host).ok())
+        .filter(|config| config.provider == provider);
+
+    match (configs.next(), configs.next()) {
+        (Some(config), None) => Ok(config),
+        _ => Err(Error::HostNotSpecified(provider)),
+    }
 }
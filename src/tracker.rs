@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::Error;
+
+/// An issue of any tracker, keyed the way it's referenced in branches.
+pub struct TrackedIssue {
+    pub key: String,
+    pub title: String,
+    pub state: String,
+    pub assignee: Option<String>,
+    pub description: Option<String>,
+    pub url: Url,
+}
+
+/// Where tickets live: Jira, or GitHub and GitLab issues.
+pub trait IssueTracker {
+    /// Open issues assigned to the current user.
+    async fn list(&self) -> Result<Vec<TrackedIssue>, Error>;
+
+    async fn get(&self, key: &str) -> Result<TrackedIssue, Error>;
+
+    async fn create(&self, title: &str, description: Option<&str>) -> Result<TrackedIssue, Error>;
+
+    async fn close(&self, key: &str) -> Result<TrackedIssue, Error>;
+
+    /// States of `keys` for the PR tables, the keys that can't be found are skipped.
+    async fn states(&self, keys: &[&str]) -> HashMap<String, String>;
+
+    fn url(&self, key: &str) -> Url;
+}
+
+/// Number of a GitHub or GitLab issue referenced as `123` or `#123`.
+pub fn issue_number(key: &str) -> Result<u32, Error> {
+    key.trim_start_matches('#')
+        .parse()
+        .map_err(|_| Error::InvalidIssueKey(key.to_string()))
+}
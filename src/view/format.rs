@@ -0,0 +1,112 @@
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use prettytable::{Row, Table};
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl ValueEnum for Format {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Format::Table, Format::Json, Format::Csv, Format::Tsv]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Format::Table => Some(PossibleValue::new("table")),
+            Format::Json => Some(PossibleValue::new("json")),
+            Format::Csv => Some(PossibleValue::new("csv")),
+            Format::Tsv => Some(PossibleValue::new("tsv")),
+        }
+    }
+}
+
+pub trait Record {
+    const HEADER: &'static [&'static str];
+
+    fn record(&self) -> Vec<String>;
+}
+
+pub fn print<T>(items: &[T], format: Format)
+where
+    T: Serialize + Record,
+{
+    match format {
+        Format::Table => {
+            let mut table = Table::new();
+            table.set_titles(Row::from(T::HEADER));
+
+            for item in items {
+                table.add_row(Row::from(item.record()));
+            }
+
+            table.printstd();
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(items).unwrap()),
+        Format::Csv => print_separated(items, ',', escape_csv),
+        Format::Tsv => print_separated(items, '\t', escape_tsv),
+    }
+}
+
+pub fn print_one<T>(item: &T, format: Format)
+where
+    T: Serialize + Record,
+{
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(item).unwrap()),
+        _ => print(std::slice::from_ref(item), format),
+    }
+}
+
+fn print_separated<T, E>(items: &[T], separator: char, escape: E)
+where
+    T: Record,
+    E: Fn(&str) -> String,
+{
+    let separator = separator.to_string();
+
+    let header = T::HEADER.iter().map(|h| escape(h)).collect::<Vec<_>>();
+    println!("{}", header.join(&separator));
+
+    for item in items {
+        let record = item.record();
+        let record = record.iter().map(|v| escape(v)).collect::<Vec<_>>();
+        println!("{}", record.join(&separator));
+    }
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_tsv(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_escaping() {
+        assert_eq!(escape_csv("IOS-1212: Test title"), "IOS-1212: Test title");
+        assert_eq!(escape_csv("a, b"), "\"a, b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn tsv_escaping() {
+        assert_eq!(escape_tsv("a\tb\nc"), "a b c");
+    }
+}
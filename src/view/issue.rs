@@ -0,0 +1,159 @@
+use serde::Serialize;
+use url::Url;
+
+use super::{wrap_title, CiStatus, Format, PullRequestView, Record};
+
+#[derive(Serialize)]
+pub struct IssueView {
+    pub key: String,
+    pub url: Url,
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub priority: Option<String>,
+    pub sprint: Option<String>,
+    pub description: Option<String>,
+
+    /// PRs whose branch or title references the ticket
+    pub prs: Vec<PullRequestView>,
+}
+
+impl Record for IssueView {
+    const HEADER: &'static [&'static str] = &[
+        "key",
+        "url",
+        "summary",
+        "status",
+        "assignee",
+        "priority",
+        "sprint",
+        "description",
+        "prs",
+    ];
+
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.url.to_string(),
+            self.summary.clone(),
+            self.status.clone(),
+            self.assignee.clone().unwrap_or_default(),
+            self.priority.clone().unwrap_or_default(),
+            self.sprint.clone().unwrap_or_default(),
+            self.description.clone().unwrap_or_default(),
+            self.prs
+                .iter()
+                .map(|pr| pr.url.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+        ]
+    }
+}
+
+/// A row of `issue list`.
+#[derive(Serialize)]
+pub struct IssueRowView {
+    pub key: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub summary: String,
+}
+
+impl Record for IssueRowView {
+    const HEADER: &'static [&'static str] = &["key", "status", "assignee", "summary"];
+
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.status.clone(),
+            self.assignee.clone().unwrap_or_default(),
+            self.summary.clone(),
+        ]
+    }
+}
+
+/// A row of `tickets`: an issue and where the work on it is.
+#[derive(Serialize)]
+pub struct TicketRowView {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub branches: Vec<String>,
+    pub pr: Option<u16>,
+    pub ci: Option<CiStatus>,
+}
+
+impl Record for TicketRowView {
+    const HEADER: &'static [&'static str] = &["ticket", "summary", "status", "branch", "pr", "ci"];
+
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.summary.clone(),
+            self.status.clone(),
+            self.branches.join(", "),
+            self.pr.map(|id| id.to_string()).unwrap_or_default(),
+            self.ci
+                .map(|ci| ci.as_str().to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct TransitionView {
+    pub id: String,
+    pub name: String,
+    pub to: String,
+}
+
+impl Record for TransitionView {
+    const HEADER: &'static [&'static str] = &["id", "name", "to"];
+
+    fn record(&self) -> Vec<String> {
+        vec![self.id.clone(), self.name.clone(), self.to.clone()]
+    }
+}
+
+pub fn print_issue(issue: &IssueView, format: Format) {
+    if format != Format::Table {
+        super::print_one(issue, format);
+        return;
+    }
+
+    println!("{}: {}", issue.key, issue.summary);
+    println!("{}", issue.url);
+    println!();
+
+    let fields = [
+        ("Status", Some(&issue.status)),
+        ("Assignee", issue.assignee.as_ref()),
+        ("Priority", issue.priority.as_ref()),
+        ("Sprint", issue.sprint.as_ref()),
+    ];
+
+    for (name, value) in fields {
+        println!(
+            "{:<10}{}",
+            format!("{name}:"),
+            value.map(String::as_str).unwrap_or("-")
+        );
+    }
+
+    if !issue.prs.is_empty() {
+        println!();
+        println!("Pull requests:");
+
+        for pr in &issue.prs {
+            println!("  {:>6}  {:<7} {}", pr.id, pr.state.as_str(), pr.title);
+            println!("  {:>6}  {}", "", pr.url);
+        }
+    }
+
+    if let Some(description) = issue.description.as_deref().map(str::trim) {
+        if !description.is_empty() {
+            println!();
+            println!("{}", wrap_title(description, 100));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+mod format;
+mod issue;
+mod picker;
+mod prompt;
+mod stats;
+mod table;
+mod worktree;
+
+pub use format::{print, print_one, Format, Record};
+pub use issue::{print_issue, IssueRowView, IssueView, TicketRowView, TransitionView};
+pub use picker::{copy_to_clipboard, pick, Action};
+pub use prompt::{ask_diverged, confirm, DivergedAction};
+pub use stats::{print_stats, PullRequestHistory, PullRequestStats};
+pub use table::{Column, Output};
+pub use worktree::WorktreeRowView;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use url::Url;
+
+#[derive(Serialize)]
+pub struct PullRequestView {
+    pub repository: String,
+
+    pub id: u16,
+    pub title: String,
+    pub url: Url,
+
+    pub author: UserView,
+
+    pub source: String,
+    pub target: String,
+
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+
+    pub state: PullRequestState,
+    pub ci: Option<CiStatus>,
+    pub approvals: Option<ApprovalsView>,
+    pub mergeability: Option<Mergeability>,
+    pub ticket: Option<TicketView>,
+
+    pub labels: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct UserView {
+    pub login: String,
+    pub name: Option<String>,
+}
+
+impl UserView {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.login)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestState {
+    Open,
+    Merged,
+    Closed,
+}
+
+impl PullRequestState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullRequestState::Open => "open",
+            PullRequestState::Merged => "merged",
+            PullRequestState::Closed => "closed",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    Pending,
+    Running,
+    Passed,
+    Failed,
+}
+
+impl CiStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CiStatus::Pending => "pending",
+            CiStatus::Running => "running",
+            CiStatus::Passed => "passed",
+            CiStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mergeability {
+    Mergeable,
+    Conflicts,
+    Behind,
+    BlockedByChecks,
+    BlockedByDiscussions,
+    Blocked,
+    Draft,
+}
+
+impl Mergeability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mergeability::Mergeable => "mergeable",
+            Mergeability::Conflicts => "conflicts",
+            Mergeability::Behind => "behind",
+            Mergeability::BlockedByChecks => "blocked_by_checks",
+            Mergeability::BlockedByDiscussions => "blocked_by_discussions",
+            Mergeability::Blocked => "blocked",
+            Mergeability::Draft => "draft",
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ApprovalsView {
+    pub approved: u16,
+    pub reviewers: Option<u16>,
+    pub required: Option<u16>,
+}
+
+impl ApprovalsView {
+    /// Whether the PR has as many approvals as the project requires,
+    /// a single approval is enough when the requirement is unknown.
+    pub fn is_met(&self) -> bool {
+        self.approved >= self.required.unwrap_or(1)
+    }
+}
+
+impl fmt::Display for ApprovalsView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reviewers {
+            Some(reviewers) => write!(f, "{}/{}", self.approved, reviewers),
+            None => write!(f, "{}", self.approved),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TicketView {
+    pub key: String,
+    pub status: Option<String>,
+}
+
+impl TicketView {
+    pub fn new(key: &str, statuses: &HashMap<String, String>) -> Self {
+        TicketView {
+            key: key.to_string(),
+            status: statuses.get(key).cloned(),
+        }
+    }
+}
+
+impl Record for PullRequestView {
+    const HEADER: &'static [&'static str] = &[
+        "repository",
+        "id",
+        "title",
+        "url",
+        "author",
+        "source",
+        "target",
+        "created",
+        "updated",
+        "state",
+        "ci",
+        "approvals",
+        "approvals_required",
+        "mergeability",
+        "ticket",
+        "ticket_status",
+        "labels",
+    ];
+
+    fn record(&self) -> Vec<String> {
+        let ticket = self.ticket.as_ref();
+
+        vec![
+            self.repository.clone(),
+            self.id.to_string(),
+            self.title.clone(),
+            self.url.to_string(),
+            self.author.login.clone(),
+            self.source.clone(),
+            self.target.clone(),
+            self.created.to_rfc3339(),
+            self.updated.to_rfc3339(),
+            self.state.as_str().to_string(),
+            self.ci
+                .map(|ci| ci.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            self.approvals
+                .as_ref()
+                .map(|approvals| approvals.approved.to_string())
+                .unwrap_or_default(),
+            self.approvals
+                .as_ref()
+                .and_then(|approvals| approvals.required)
+                .map(|required| required.to_string())
+                .unwrap_or_default(),
+            self.mergeability
+                .map(|mergeability| mergeability.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            ticket.map(|t| t.key.clone()).unwrap_or_default(),
+            ticket.and_then(|t| t.status.clone()).unwrap_or_default(),
+            self.labels.join(","),
+        ]
+    }
+}
+
+impl Record for UserView {
+    const HEADER: &'static [&'static str] = &["login", "name"];
+
+    fn record(&self) -> Vec<String> {
+        vec![self.login.clone(), self.name.clone().unwrap_or_default()]
+    }
+}
+
+pub fn wrap_title(title: &str, max_width: usize) -> String {
+    use hyphenation::{Language, Load, Standard};
+    use textwrap::{fill, Options, WordSplitter::Hyphenation};
+
+    let hyphenator = Standard::from_embedded(Language::EnglishUS).unwrap();
+    let options = Options::new(max_width).word_splitter(Hyphenation(hyphenator));
+
+    fill(title, options)
+}
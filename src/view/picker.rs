@@ -0,0 +1,349 @@
+use std::cmp::Reverse;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crossterm::cursor::MoveTo;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use super::PullRequestView;
+use crate::Error;
+
+const HELP: &str = "enter: checkout  ctrl-o: browse  tab: info  ctrl-y: copy URL  esc: cancel";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Checkout,
+    Browse,
+    Info,
+    CopyUrl,
+}
+
+impl Action {
+    fn from_key(key: &KeyEvent) -> Option<Action> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, _) => Some(Action::Checkout),
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) => Some(Action::Browse),
+            (KeyCode::Tab, _) => Some(Action::Info),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => Some(Action::CopyUrl),
+            _ => None,
+        }
+    }
+
+    fn from_answer(answer: &str) -> Option<Action> {
+        match answer {
+            "c" | "checkout" => Some(Action::Checkout),
+            "b" | "browse" => Some(Action::Browse),
+            "i" | "info" => Some(Action::Info),
+            "y" | "copy" => Some(Action::CopyUrl),
+            _ => None,
+        }
+    }
+}
+
+/// Lets the user pick one of `prs` and an action to perform on it.
+///
+/// Shows a filterable list when stdout is a terminal, otherwise falls back
+/// to a numbered prompt on stderr. Returns `None` if the user cancelled.
+pub fn pick(prs: &[PullRequestView]) -> Result<Option<(&PullRequestView, Action)>, Error> {
+    if prs.is_empty() {
+        return Ok(None);
+    }
+
+    let labels = prs.iter().map(label).collect::<Vec<_>>();
+
+    let picked = if io::stdout().is_terminal() {
+        interactive(&labels).map_err(Error::Terminal)?
+    } else {
+        prompt(&labels)?
+    };
+
+    Ok(picked.map(|(index, action)| (&prs[index], action)))
+}
+
+/// Commands that put their stdin on the clipboard, macOS, Wayland and X11 ones.
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+pub fn copy_to_clipboard(text: &str) -> Result<(), Error> {
+    let mut child = CLIPBOARD_COMMANDS
+        .iter()
+        .find_map(|command| {
+            Command::new(command[0])
+                .args(&command[1..])
+                .stdin(Stdio::piped())
+                .spawn()
+                .ok()
+        })
+        .ok_or(Error::NoClipboard)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(Error::CopyToClipboard)?;
+    }
+
+    let status = child.wait().map_err(Error::CopyToClipboard)?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::CopyToClipboard(io::Error::other(format!(
+            "the clipboard command exited with {status}"
+        ))))
+    }
+}
+
+fn label(pr: &PullRequestView) -> String {
+    format!(
+        "#{} {} ({}, {} -> {})",
+        pr.id,
+        pr.title,
+        pr.author.display_name(),
+        pr.source,
+        pr.target
+    )
+}
+
+fn interactive(labels: &[String]) -> io::Result<Option<(usize, Action)>> {
+    let mut stdout = io::stdout();
+    let _screen = RawScreen::enter(&mut stdout)?;
+
+    Picker::new(labels).run(&mut stdout)
+}
+
+/// Raw mode in the alternate screen, left however the picker ends.
+struct RawScreen;
+
+impl RawScreen {
+    fn enter(stdout: &mut io::Stdout) -> io::Result<RawScreen> {
+        terminal::enable_raw_mode()?;
+        // dropped on errors too, raw mode is disabled if the alternate screen fails
+        let screen = RawScreen;
+        execute!(stdout, EnterAlternateScreen)?;
+
+        Ok(screen)
+    }
+}
+
+impl Drop for RawScreen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn prompt(labels: &[String]) -> Result<Option<(usize, Action)>, Error> {
+    let mut stderr = io::stderr();
+
+    for (index, label) in labels.iter().enumerate() {
+        eprintln!("{:>3}) {}", index + 1, label);
+    }
+
+    eprint!(
+        "Pick a PR [1-{}] and an action: (c)heckout, (b)rowse, (i)nfo, cop(y) URL: ",
+        labels.len()
+    );
+    stderr.flush().map_err(Error::Terminal)?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .map_err(Error::Terminal)?;
+
+    parse_answer(&answer, labels.len())
+}
+
+fn parse_answer(answer: &str, count: usize) -> Result<Option<(usize, Action)>, Error> {
+    let mut parts = answer.split_whitespace();
+
+    let number = match parts.next() {
+        Some(number) => number,
+        None => return Ok(None),
+    };
+
+    let index = number
+        .parse::<usize>()
+        .ok()
+        .filter(|number| (1..=count).contains(number))
+        .ok_or_else(|| Error::InvalidChoice(answer.trim().to_string()))?;
+
+    let action = match parts.next() {
+        Some(action) => Action::from_answer(action)
+            .ok_or_else(|| Error::InvalidChoice(answer.trim().to_string()))?,
+        None => Action::Checkout,
+    };
+
+    Ok(Some((index - 1, action)))
+}
+
+struct Picker<'a> {
+    labels: &'a [String],
+    matcher: SkimMatcherV2,
+
+    query: String,
+    matches: Vec<usize>,
+
+    selected: usize,
+    offset: usize,
+}
+
+impl<'a> Picker<'a> {
+    fn new(labels: &'a [String]) -> Self {
+        let mut picker = Picker {
+            labels,
+            matcher: SkimMatcherV2::default(),
+            query: String::new(),
+            matches: vec![],
+            selected: 0,
+            offset: 0,
+        };
+
+        picker.filter();
+        picker
+    }
+
+    fn filter(&mut self) {
+        let mut scored = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, label)| {
+                if self.query.is_empty() {
+                    Some((0, index))
+                } else {
+                    self.matcher
+                        .fuzzy_match(label, &self.query)
+                        .map(|score| (score, index))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        scored.sort_by_key(|(score, index)| (Reverse(*score), *index));
+
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn run(&mut self, out: &mut impl Write) -> io::Result<Option<(usize, Action)>> {
+        loop {
+            self.draw(out)?;
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+
+            if let Some(action) = Action::from_key(&key) {
+                match self.matches.get(self.selected) {
+                    Some(index) => return Ok(Some((*index, action))),
+                    None => continue,
+                }
+            }
+
+            match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(None),
+                (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL)
+                    if self.selected + 1 < self.matches.len() =>
+                {
+                    self.selected += 1;
+                }
+                (KeyCode::Backspace, _) if self.query.pop().is_some() => self.filter(),
+                (KeyCode::Char(c), modifiers) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.query.push(c);
+                    self.filter();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let width = width as usize;
+        let rows = (height as usize).saturating_sub(2).max(1);
+
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+
+        queue!(out, Clear(ClearType::All))?;
+
+        for (row, index) in self.matches.iter().enumerate().skip(self.offset).take(rows) {
+            let line = truncate(&self.labels[*index], width.saturating_sub(2));
+            queue!(out, MoveTo(0, (row - self.offset + 1) as u16))?;
+
+            if row == self.selected {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(format!("> {line}")),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(format!("  {line}")))?;
+            }
+        }
+
+        queue!(
+            out,
+            MoveTo(0, height.saturating_sub(1)),
+            SetAttribute(Attribute::Dim),
+            Print(truncate(HELP, width)),
+            SetAttribute(Attribute::Reset),
+            MoveTo(0, 0),
+            Print(format!(
+                "{}/{} > {}",
+                self.matches.len(),
+                self.labels.len(),
+                self.query
+            ))
+        )?;
+
+        out.flush()
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbered_answers() {
+        assert_eq!(parse_answer("2\n", 3).unwrap(), Some((1, Action::Checkout)));
+        assert_eq!(
+            parse_answer(" 3 b\n", 3).unwrap(),
+            Some((2, Action::Browse))
+        );
+        assert_eq!(parse_answer("1 y", 3).unwrap(), Some((0, Action::CopyUrl)));
+        assert_eq!(parse_answer("\n", 3).unwrap(), None);
+
+        assert!(matches!(
+            parse_answer("4", 3),
+            Err(Error::InvalidChoice(answer)) if answer == "4"
+        ));
+        assert!(matches!(
+            parse_answer("1 x", 3),
+            Err(Error::InvalidChoice(_))
+        ));
+    }
+}
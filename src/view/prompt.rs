@@ -0,0 +1,97 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::Error;
+
+/// What to do with a local branch that has diverged from the one being checked out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DivergedAction {
+    Keep,
+    Reset,
+    Rename,
+}
+
+impl DivergedAction {
+    fn from_answer(answer: &str) -> Option<DivergedAction> {
+        match answer.trim() {
+            "" | "k" | "keep" => Some(DivergedAction::Keep),
+            "r" | "reset" => Some(DivergedAction::Reset),
+            "n" | "rename" => Some(DivergedAction::Rename),
+            _ => None,
+        }
+    }
+}
+
+/// Asks what to do with `branch` that has `ahead` commits of its own and misses `behind`.
+///
+/// Keeps the branch as it is when there's nobody to ask.
+pub fn ask_diverged(
+    branch: &str,
+    ahead: usize,
+    behind: usize,
+    backup: &str,
+) -> Result<DivergedAction, Error> {
+    eprintln!(
+        "warning: local branch {branch} has diverged, it has {ahead} commit(s) of its own and misses {behind}"
+    );
+
+    if !io::stdin().is_terminal() {
+        eprintln!("keeping {branch} as it is");
+        return Ok(DivergedAction::Keep);
+    }
+
+    eprintln!("  (k)eep it as it is");
+    eprintln!("  (r)eset it, its commits stay on {backup}");
+    eprintln!("  re(n)ame it to {backup} and start {branch} over");
+    eprint!("What to do with {branch}? [K/r/n]: ");
+
+    let answer = read_answer()?;
+
+    DivergedAction::from_answer(&answer)
+        .ok_or_else(|| Error::InvalidChoice(answer.trim().to_string()))
+}
+
+/// Asks a yes or no `question`, `false` when there's nobody to ask.
+pub fn confirm(question: &str) -> Result<bool, Error> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    eprint!("{question} [y/N]: ");
+    let answer = read_answer()?;
+
+    Ok(matches!(answer.trim(), "y" | "yes"))
+}
+
+fn read_answer() -> Result<String, Error> {
+    io::stderr().flush().map_err(Error::Terminal)?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .map_err(Error::Terminal)?;
+
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diverged_answers() {
+        assert_eq!(
+            DivergedAction::from_answer("\n"),
+            Some(DivergedAction::Keep)
+        );
+        assert_eq!(
+            DivergedAction::from_answer("r\n"),
+            Some(DivergedAction::Reset)
+        );
+        assert_eq!(
+            DivergedAction::from_answer(" rename "),
+            Some(DivergedAction::Rename)
+        );
+        assert_eq!(DivergedAction::from_answer("x"), None);
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use prettytable::{row, Table};
+use serde::Serialize;
+
+use super::{Format, Record};
+
+/// Review history of a single merged or closed PR, collected by the providers.
+pub struct PullRequestHistory {
+    pub created: DateTime<Utc>,
+    pub merged: Option<DateTime<Utc>>,
+    pub first_review: Option<DateTime<Utc>>,
+
+    /// Number of changed files
+    pub size: Option<u32>,
+    /// Everyone except the author who reviewed, approved or commented
+    pub reviewers: Vec<String>,
+
+    pub ci_runs: u32,
+    pub ci_failures: u32,
+}
+
+#[derive(Serialize)]
+pub struct PullRequestStats {
+    pub repository: String,
+    pub since: DateTime<Utc>,
+
+    pub total: usize,
+    pub merged: usize,
+    pub closed: usize,
+
+    pub hours_to_first_review: Option<Summary>,
+    pub hours_to_merge: Option<Summary>,
+    pub size: Option<Summary>,
+
+    pub ci_runs: u32,
+    pub ci_failures: u32,
+    pub ci_failure_rate: Option<f64>,
+
+    pub reviewers: Vec<ReviewerStats>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Summary {
+    pub median: f64,
+    pub mean: f64,
+    pub p90: f64,
+}
+
+#[derive(Serialize)]
+pub struct ReviewerStats {
+    pub reviewer: String,
+    pub reviews: usize,
+}
+
+impl PullRequestStats {
+    pub fn new(repository: String, since: DateTime<Utc>, prs: &[PullRequestHistory]) -> Self {
+        let merged = prs.iter().filter(|pr| pr.merged.is_some()).count();
+
+        let hours_to_first_review = prs
+            .iter()
+            .filter_map(|pr| Some(hours_between(pr.created, pr.first_review?)))
+            .collect::<Vec<_>>();
+
+        let hours_to_merge = prs
+            .iter()
+            .filter_map(|pr| Some(hours_between(pr.created, pr.merged?)))
+            .collect::<Vec<_>>();
+
+        let sizes = prs
+            .iter()
+            .filter_map(|pr| pr.size)
+            .map(f64::from)
+            .collect::<Vec<_>>();
+
+        let ci_runs = prs.iter().map(|pr| pr.ci_runs).sum::<u32>();
+        let ci_failures = prs.iter().map(|pr| pr.ci_failures).sum::<u32>();
+
+        let mut reviews = HashMap::<&str, usize>::new();
+        for reviewer in prs.iter().flat_map(|pr| pr.reviewers.iter()) {
+            *reviews.entry(reviewer).or_default() += 1;
+        }
+
+        let mut reviewers = reviews
+            .into_iter()
+            .map(|(reviewer, reviews)| ReviewerStats {
+                reviewer: reviewer.to_string(),
+                reviews,
+            })
+            .collect::<Vec<_>>();
+        reviewers.sort_by(|a, b| b.reviews.cmp(&a.reviews).then(a.reviewer.cmp(&b.reviewer)));
+
+        PullRequestStats {
+            repository,
+            since,
+            total: prs.len(),
+            merged,
+            closed: prs.len() - merged,
+            hours_to_first_review: Summary::new(hours_to_first_review),
+            hours_to_merge: Summary::new(hours_to_merge),
+            size: Summary::new(sizes),
+            ci_runs,
+            ci_failures,
+            ci_failure_rate: if ci_runs > 0 {
+                Some(f64::from(ci_failures) / f64::from(ci_runs))
+            } else {
+                None
+            },
+            reviewers,
+        }
+    }
+}
+
+impl Summary {
+    fn new(mut values: Vec<f64>) -> Option<Summary> {
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        Some(Summary {
+            median: percentile(&values, 0.5),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            p90: percentile(&values, 0.9),
+        })
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn hours_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_minutes() as f64 / 60.0
+}
+
+impl Record for PullRequestStats {
+    const HEADER: &'static [&'static str] = &[
+        "repository",
+        "since",
+        "total",
+        "merged",
+        "closed",
+        "hours_to_first_review_median",
+        "hours_to_first_review_mean",
+        "hours_to_merge_median",
+        "hours_to_merge_mean",
+        "size_median",
+        "size_mean",
+        "ci_failure_rate",
+    ];
+
+    fn record(&self) -> Vec<String> {
+        fn value(value: Option<f64>) -> String {
+            value.map(|v| format!("{v:.2}")).unwrap_or_default()
+        }
+
+        let first_review = self.hours_to_first_review.as_ref();
+        let merge = self.hours_to_merge.as_ref();
+        let size = self.size.as_ref();
+
+        vec![
+            self.repository.clone(),
+            self.since.to_rfc3339(),
+            self.total.to_string(),
+            self.merged.to_string(),
+            self.closed.to_string(),
+            value(first_review.map(|s| s.median)),
+            value(first_review.map(|s| s.mean)),
+            value(merge.map(|s| s.median)),
+            value(merge.map(|s| s.mean)),
+            value(size.map(|s| s.median)),
+            value(size.map(|s| s.mean)),
+            value(self.ci_failure_rate),
+        ]
+    }
+}
+
+pub fn print_stats(stats: &PullRequestStats, format: Format) {
+    if format != Format::Table {
+        super::print_one(stats, format);
+        return;
+    }
+
+    println!(
+        "{} PRs in {} since {} ({} merged, {} closed)",
+        stats.total,
+        stats.repository,
+        stats.since.format("%Y-%m-%d"),
+        stats.merged,
+        stats.closed
+    );
+
+    if stats.total == 0 {
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row!["", "Median", "Mean", "90th percentile"]);
+
+    let durations = [
+        ("Time to first review", &stats.hours_to_first_review),
+        ("Time to merge", &stats.hours_to_merge),
+    ];
+
+    for (title, summary) in durations {
+        match summary {
+            Some(s) => table.add_row(row![
+                title,
+                format_hours(s.median),
+                format_hours(s.mean),
+                format_hours(s.p90)
+            ]),
+            None => table.add_row(row![title, "-", "-", "-"]),
+        };
+    }
+
+    match &stats.size {
+        Some(s) => table.add_row(row![
+            "Files changed",
+            format!("{:.0}", s.median),
+            format!("{:.1}", s.mean),
+            format!("{:.0}", s.p90)
+        ]),
+        None => table.add_row(row!["Files changed", "-", "-", "-"]),
+    };
+
+    table.printstd();
+
+    if let Some(rate) = stats.ci_failure_rate {
+        println!(
+            "CI failure rate: {:.1}% ({} of {} runs)",
+            rate * 100.0,
+            stats.ci_failures,
+            stats.ci_runs
+        );
+    }
+
+    if stats.reviewers.is_empty() {
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row!["Reviewer", "Reviews"]);
+
+    for reviewer in &stats.reviewers {
+        table.add_row(row![reviewer.reviewer, reviewer.reviews]);
+    }
+
+    table.printstd();
+}
+
+fn format_hours(hours: f64) -> String {
+    let minutes = (hours * 60.0).round() as i64;
+
+    match (minutes / (24 * 60), minutes / 60 % 24, minutes % 60) {
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, h, _) => format!("{d}d {h}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn history(
+        hours_to_review: Option<i64>,
+        hours_to_merge: Option<i64>,
+        reviewers: &[&str],
+    ) -> PullRequestHistory {
+        let created = Utc.with_ymd_and_hms(2024, 10, 1, 10, 0, 0).unwrap();
+
+        PullRequestHistory {
+            created,
+            merged: hours_to_merge.map(|h| created + Duration::hours(h)),
+            first_review: hours_to_review.map(|h| created + Duration::hours(h)),
+            size: Some(reviewers.len() as u32 * 10),
+            reviewers: reviewers.iter().map(|r| r.to_string()).collect(),
+            ci_runs: 2,
+            ci_failures: 1,
+        }
+    }
+
+    #[test]
+    fn aggregation() {
+        let prs = vec![
+            history(Some(1), Some(10), &["alice", "bob"]),
+            history(Some(3), Some(20), &["alice"]),
+            history(Some(2), None, &[]),
+            history(None, Some(30), &["bob", "carol"]),
+        ];
+
+        let since = Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap();
+        let stats = PullRequestStats::new("chipp/gitext".to_string(), since, &prs);
+
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.merged, 3);
+        assert_eq!(stats.closed, 1);
+
+        assert_eq!(
+            stats.hours_to_first_review,
+            Some(Summary {
+                median: 2.0,
+                mean: 2.0,
+                p90: 3.0
+            })
+        );
+        assert_eq!(
+            stats.hours_to_merge,
+            Some(Summary {
+                median: 20.0,
+                mean: 20.0,
+                p90: 30.0
+            })
+        );
+
+        assert_eq!(stats.ci_runs, 8);
+        assert_eq!(stats.ci_failure_rate, Some(0.5));
+
+        let reviewers = stats
+            .reviewers
+            .iter()
+            .map(|r| (r.reviewer.as_str(), r.reviews))
+            .collect::<Vec<_>>();
+        assert_eq!(reviewers, vec![("alice", 2), ("bob", 2), ("carol", 1)]);
+    }
+
+    #[test]
+    fn hours_formatting() {
+        assert_eq!(format_hours(0.5), "30m");
+        assert_eq!(format_hours(2.25), "2h 15m");
+        assert_eq!(format_hours(50.0), "2d 2h");
+    }
+}
@@ -0,0 +1,505 @@
+use std::str::FromStr;
+
+use clap::builder::PossibleValue;
+use clap::{ArgMatches, ValueEnum};
+use prettytable::{Cell, Row, Table};
+
+use super::{wrap_title, CiStatus, Format, Mergeability, PullRequestState, PullRequestView};
+use crate::git::{JiraUrlConfig, Tracker, TrackerConfig};
+use crate::Error;
+
+const TITLE_WIDTH: usize = 35;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    Repository,
+    Id,
+    Author,
+    Title,
+    Ci,
+    Approvals,
+    Merge,
+    Source,
+    Target,
+    Updated,
+    State,
+    Ticket,
+    Jira,
+    Labels,
+    Url,
+}
+
+impl ValueEnum for Column {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Column::Repository,
+            Column::Id,
+            Column::Author,
+            Column::Title,
+            Column::Ci,
+            Column::Approvals,
+            Column::Merge,
+            Column::Source,
+            Column::Target,
+            Column::Updated,
+            Column::State,
+            Column::Ticket,
+            Column::Jira,
+            Column::Labels,
+            Column::Url,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+impl Column {
+    /// Columns shown by `prs` when neither `--columns` nor `gitext.prs.columns` is set.
+    pub fn defaults<Conf>(config: &Conf) -> Vec<Column>
+    where
+        Conf: JiraUrlConfig,
+        Conf: TrackerConfig,
+    {
+        let mut columns = vec![
+            Column::Id,
+            Column::Author,
+            Column::Title,
+            Column::Ci,
+            Column::Approvals,
+            Column::Merge,
+            Column::Target,
+            Column::Updated,
+        ];
+
+        if config.jira_url().is_some() || config.tracker() == Tracker::Issues {
+            columns.push(Column::Jira);
+        }
+
+        columns
+    }
+
+    pub fn parse_list(list: &str) -> Result<Vec<Column>, Error> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                <Column as ValueEnum>::from_str(name, true)
+                    .map_err(|_| Error::UnknownColumn(name.to_string()))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Column::Repository => "repository",
+            Column::Id => "id",
+            Column::Author => "author",
+            Column::Title => "title",
+            Column::Ci => "ci",
+            Column::Approvals => "approvals",
+            Column::Merge => "merge",
+            Column::Source => "source",
+            Column::Target => "target",
+            Column::Updated => "updated",
+            Column::State => "state",
+            Column::Ticket => "ticket",
+            Column::Jira => "jira",
+            Column::Labels => "labels",
+            Column::Url => "url",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Repository => "Repository",
+            Column::Id => "ID",
+            Column::Author => "Author",
+            Column::Title => "Title",
+            Column::Ci => "CI",
+            Column::Approvals => "Approvals",
+            Column::Merge => "Merge",
+            Column::Source => "Source",
+            Column::Target => "Target",
+            Column::Updated => "Last updated",
+            Column::State => "Status",
+            Column::Ticket => "Ticket",
+            Column::Jira => "Ticket status",
+            Column::Labels => "Labels",
+            Column::Url => "URL",
+        }
+    }
+
+    fn text(&self, pr: &PullRequestView) -> String {
+        match self {
+            Column::Repository => pr.repository.clone(),
+            Column::Id => pr.id.to_string(),
+            Column::Author => pr.author.display_name().to_string(),
+            Column::Title => pr.title.clone(),
+            Column::Ci => pr.ci.map(|ci| ci.as_str()).unwrap_or_default().to_string(),
+            Column::Approvals => pr
+                .approvals
+                .as_ref()
+                .map(|approvals| approvals.to_string())
+                .unwrap_or_default(),
+            Column::Merge => pr
+                .mergeability
+                .map(|mergeability| mergeability.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            Column::Source => pr.source.clone(),
+            Column::Target => pr.target.clone(),
+            Column::Updated => {
+                let updated = pr.updated - chrono::Utc::now();
+                chrono_humanize::HumanTime::from(updated).to_string()
+            }
+            Column::State => pr.state.as_str().to_string(),
+            Column::Ticket => pr
+                .ticket
+                .as_ref()
+                .map(|ticket| ticket.key.clone())
+                .unwrap_or_default(),
+            Column::Jira => pr
+                .ticket
+                .as_ref()
+                .and_then(|ticket| ticket.status.clone())
+                .unwrap_or_else(|| String::from("N/A")),
+            Column::Labels => pr.labels.join(", "),
+            Column::Url => pr.url.to_string(),
+        }
+    }
+
+    fn cell(&self, pr: &PullRequestView) -> Cell {
+        match self {
+            Column::Title => Cell::new(&wrap_title(&pr.title, TITLE_WIDTH)),
+            Column::Ci => match pr.ci {
+                Some(CiStatus::Pending) => Cell::new("P").style_spec("Fy"),
+                Some(CiStatus::Running) => Cell::new("R").style_spec("Fy"),
+                Some(CiStatus::Passed) => Cell::new("A").style_spec("Fg"),
+                Some(CiStatus::Failed) => Cell::new("X").style_spec("Fr"),
+                None => Cell::new(" "),
+            },
+            Column::Approvals => match &pr.approvals {
+                Some(approvals) if approvals.is_met() => {
+                    Cell::new(&approvals.to_string()).style_spec("Fg")
+                }
+                Some(approvals) => Cell::new(&approvals.to_string()).style_spec("Fr"),
+                None => Cell::new(" "),
+            },
+            Column::Merge => match pr.mergeability {
+                Some(Mergeability::Mergeable) => Cell::new("OK").style_spec("Fg"),
+                Some(Mergeability::Conflicts) => Cell::new("Conflicts").style_spec("Fr"),
+                Some(Mergeability::Behind) => Cell::new("Behind").style_spec("Fy"),
+                Some(Mergeability::BlockedByChecks) => Cell::new("Checks").style_spec("Fr"),
+                Some(Mergeability::BlockedByDiscussions) => {
+                    Cell::new("Discussions").style_spec("Fy")
+                }
+                Some(Mergeability::Blocked) => Cell::new("Blocked").style_spec("Fr"),
+                Some(Mergeability::Draft) => Cell::new("Draft").style_spec("Fy"),
+                None => Cell::new(" "),
+            },
+            Column::State => match pr.state {
+                PullRequestState::Open => Cell::new("Open").style_spec("Fy"),
+                PullRequestState::Merged => Cell::new("Merged").style_spec("Fg"),
+                PullRequestState::Closed => Cell::new("Closed").style_spec("Fr"),
+            },
+            Column::Labels => Cell::new(&wrap_title(&self.text(pr), TITLE_WIDTH)),
+            _ => Cell::new(&self.text(pr)),
+        }
+    }
+}
+
+/// A line template like `{id}\t{title}` where placeholders are column names.
+#[derive(Debug, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Part {
+    Literal(String),
+    Column(Column),
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut chars = raw.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+
+                    if !closed {
+                        return Err(Error::InvalidTemplate(raw.to_string()));
+                    }
+
+                    let column = <Column as ValueEnum>::from_str(name.trim(), true)
+                        .map_err(|_| Error::UnknownColumn(name.clone()))?;
+
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Column(column));
+                }
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some(c) => literal.push(c),
+                    None => literal.push('\\'),
+                },
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template { parts })
+    }
+}
+
+impl Template {
+    pub fn render(&self, pr: &PullRequestView) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.clone(),
+                Part::Column(column) => column.text(pr),
+            })
+            .collect()
+    }
+}
+
+enum Layout {
+    Columns(Vec<Column>),
+    Template(Template),
+}
+
+/// Renders pull requests according to `--format`, `--template` and `--columns`.
+pub struct Output {
+    format: Format,
+    layout: Layout,
+    /// Whether the columns are the defaults rather than chosen by the user.
+    defaults: bool,
+}
+
+impl Output {
+    pub fn from_args(
+        args: &ArgMatches,
+        configured: Option<&str>,
+        defaults: Vec<Column>,
+    ) -> Result<Output, Error> {
+        let format = args
+            .get_one::<Format>("format")
+            .copied()
+            .unwrap_or_default();
+
+        if let Ok(Some(template)) = args.try_get_one::<String>("template") {
+            return Ok(Output {
+                format,
+                layout: Layout::Template(template.parse()?),
+                defaults: false,
+            });
+        }
+
+        let (columns, defaults) = match (args.try_get_many::<Column>("columns"), configured) {
+            (Ok(Some(columns)), _) => (columns.copied().collect(), false),
+            (_, Some(configured)) => (Column::parse_list(configured)?, false),
+            (_, None) => (defaults, true),
+        };
+
+        Ok(Output {
+            format,
+            layout: Layout::Columns(columns),
+            defaults,
+        })
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Whether the user chose to show `column`, for columns that are expensive to fill.
+    pub fn selects(&self, column: Column) -> bool {
+        match &self.layout {
+            _ if self.defaults => false,
+            Layout::Columns(columns) => columns.contains(&column),
+            Layout::Template(template) => template.parts.contains(&Part::Column(column)),
+        }
+    }
+
+    pub fn print(&self, prs: &[PullRequestView], empty_message: &str) {
+        match (&self.layout, self.format) {
+            (Layout::Template(template), Format::Table) => {
+                for pr in prs {
+                    println!("{}", template.render(pr));
+                }
+            }
+            (Layout::Columns(_), Format::Table) if prs.is_empty() => println!("{empty_message}"),
+            (Layout::Columns(columns), Format::Table) => {
+                let mut table = Table::new();
+                table.set_titles(titles(columns));
+
+                for pr in prs {
+                    table.add_row(row(pr, columns));
+                }
+
+                table.printstd();
+            }
+            (_, format) => super::print(prs, format),
+        }
+    }
+
+    /// Prints pull requests of several repositories, each under its own header row.
+    pub fn print_grouped<E>(&self, groups: Vec<(&str, Result<Vec<PullRequestView>, E>)>)
+    where
+        E: std::fmt::Display,
+    {
+        let columns = match (&self.layout, self.format) {
+            (Layout::Columns(columns), Format::Table) => columns,
+            _ => {
+                let mut prs = vec![];
+
+                for (name, result) in groups {
+                    match result {
+                        Ok(views) => prs.extend(views),
+                        Err(err) => eprintln!("{name}: {err}"),
+                    }
+                }
+
+                return self.print(&prs, "");
+            }
+        };
+
+        let span = format!("bH{}", columns.len());
+
+        let mut table = Table::new();
+        table.set_titles(titles(columns));
+
+        for (name, result) in &groups {
+            match result {
+                Ok(prs) if prs.is_empty() => continue,
+                Ok(prs) => {
+                    table.add_row(Row::new(vec![Cell::new(name).style_spec(&span)]));
+
+                    for pr in prs {
+                        table.add_row(row(pr, columns));
+                    }
+                }
+                Err(err) => {
+                    table.add_row(Row::new(vec![Cell::new(name).style_spec(&span)]));
+                    table
+                        .add_row(Row::new(vec![Cell::new(&err.to_string())
+                            .style_spec(&format!("FrH{}", columns.len()))]));
+                }
+            }
+        }
+
+        if table.is_empty() {
+            println!("No open PRs in the workspace");
+            return;
+        }
+
+        table.printstd();
+    }
+}
+
+fn titles(columns: &[Column]) -> Row {
+    Row::new(
+        columns
+            .iter()
+            .map(|column| Cell::new(column.title()))
+            .collect(),
+    )
+}
+
+fn row(pr: &PullRequestView, columns: &[Column]) -> Row {
+    Row::new(columns.iter().map(|column| column.cell(pr)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::{TicketView, UserView};
+    use chrono::Utc;
+
+    fn pr() -> PullRequestView {
+        PullRequestView {
+            repository: String::from("chipp/gitext"),
+            id: 42,
+            title: String::from("IOS-1212: Test title"),
+            url: "https://github.com/chipp/gitext/pull/42".parse().unwrap(),
+            author: UserView {
+                login: String::from("chipp"),
+                name: None,
+            },
+            source: String::from("feature/IOS-1212"),
+            target: String::from("main"),
+            created: Utc::now(),
+            updated: Utc::now(),
+            state: PullRequestState::Open,
+            ci: Some(CiStatus::Passed),
+            approvals: None,
+            mergeability: None,
+            ticket: Some(TicketView {
+                key: String::from("IOS-1212"),
+                status: None,
+            }),
+            labels: vec![String::from("bug"), String::from("ui")],
+        }
+    }
+
+    #[test]
+    fn parse_columns() {
+        assert_eq!(
+            Column::parse_list("id, Title,ci,").unwrap(),
+            vec![Column::Id, Column::Title, Column::Ci]
+        );
+        assert!(matches!(
+            Column::parse_list("id,likes"),
+            Err(Error::UnknownColumn(name)) if name == "likes"
+        ));
+    }
+
+    #[test]
+    fn render_template() {
+        let template = r"{id}\t{title} [{labels}] {jira}"
+            .parse::<Template>()
+            .unwrap();
+        assert_eq!(
+            template.render(&pr()),
+            "42\tIOS-1212: Test title [bug, ui] N/A"
+        );
+
+        let template = "#{id}: {ci}".parse::<Template>().unwrap();
+        assert_eq!(template.render(&pr()), "#42: passed");
+    }
+
+    #[test]
+    fn invalid_template() {
+        assert!(matches!(
+            "{id".parse::<Template>(),
+            Err(Error::InvalidTemplate(_))
+        ));
+        assert!(matches!(
+            "{id} {likes}".parse::<Template>(),
+            Err(Error::UnknownColumn(name)) if name == "likes"
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::{PullRequestState, Record};
+
+#[derive(Serialize)]
+pub struct WorktreeRowView {
+    pub pr: u16,
+    /// `None` when the PR can't be found
+    pub state: Option<PullRequestState>,
+    pub branch: Option<String>,
+    pub path: PathBuf,
+}
+
+impl Record for WorktreeRowView {
+    const HEADER: &'static [&'static str] = &["pr", "state", "branch", "path"];
+
+    fn record(&self) -> Vec<String> {
+        vec![
+            self.pr.to_string(),
+            self.state
+                .map(|state| state.as_str().to_string())
+                .unwrap_or_default(),
+            self.branch.clone().unwrap_or_default(),
+            self.path.display().to_string(),
+        ]
+    }
+}